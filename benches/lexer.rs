@@ -0,0 +1,57 @@
+// Lexes generated scripts of growing size; with a linear lexer the time per line stays flat
+// as the script doubles. Run with `cargo bench --bench lexer`.
+use std::time::{Duration, Instant};
+
+use ion::lexer::tokenize;
+
+const SNIPPET: &str = r#"| count <asg> <structure: numeric> 10; // a comment
+| name <asg> <const> <structure: string> "ion";
+| double <asg> <structure: function> fn (a: numeric) @ numeric {
+    return a * 2;
+};
+/* a block
+   comment */
+for i in 0..count {
+    if i % 2 == 0 && i >= 2 || !(i < 1) {
+        log(name, double(i), "\n");
+    }
+}
+"#;
+
+fn script(copies: usize) -> String {
+    SNIPPET.repeat(copies)
+}
+
+fn best_of(runs: usize, source: &str) -> (Duration, usize) {
+    let mut best = Duration::MAX;
+    let mut tokens = 0;
+    for _ in 0..runs {
+        let source = source.to_string();
+        let start = Instant::now();
+        tokens = tokenize(source).expect("generated script should lex").len();
+        best = best.min(start.elapsed());
+    }
+    (best, tokens)
+}
+
+fn main() {
+    let lines_per_copy = SNIPPET.lines().count();
+    println!("{:>8} {:>10} {:>12} {:>12}", "lines", "tokens", "time (ms)", "ns / line");
+
+    let mut first: Option<f64> = None;
+    for copies in [250, 500, 1_000, 2_000, 4_000, 8_000] {
+        let source = script(copies);
+        let lines = copies * lines_per_copy;
+        let (elapsed, tokens) = best_of(5, &source);
+        let per_line = elapsed.as_nanos() as f64 / lines as f64;
+        first.get_or_insert(per_line);
+        println!("{:>8} {:>10} {:>12.2} {:>12.1}", lines, tokens, elapsed.as_secs_f64() * 1000.0, per_line);
+    }
+
+    // Quadratic lexing would make this ratio grow with the input (32x input => ~32x per line)
+    if let Some(first) = first {
+        let (elapsed, _) = best_of(5, &script(8_000));
+        let last = elapsed.as_nanos() as f64 / (8_000 * lines_per_copy) as f64;
+        println!("\nper-line cost, largest vs smallest script: {:.2}x", last / first);
+    }
+}
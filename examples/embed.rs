@@ -0,0 +1,58 @@
+// Runs Ion in-process: the host seeds a global, exposes a native function and reads results back.
+// Run with `cargo run --example embed`.
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ion::error::IonError;
+use ion::interpreter::{unwrap_runtime_value_serve, RuntimeValueServe};
+use ion::scopes::Scope;
+use ion::values::{NumericVal, StrLiteral};
+use ion::{ion_native, Interpreter};
+
+// Typed natives: arguments are checked and converted for us, `exp: 2.5` is a type error
+#[ion_native]
+fn pow(base: f64, exp: i32) -> f64 {
+    base.powi(exp)
+}
+
+#[ion_native]
+fn repeat(text: String, times: usize) -> Result<String, IonError> {
+    if times > 100 {
+        return Err(IonError::runtime(format!("repeat() refuses to build {} copies", times)));
+    }
+    Ok(text.repeat(times))
+}
+
+fn main() -> Result<(), IonError> {
+    let mut ion = Interpreter::new();
+
+    ion.set_global("base", Box::new(NumericVal{value: 21.0}))?;
+    ion.register_native("shout", |args: Vec<RuntimeValueServe>, scope: &Rc<RefCell<Scope>>| {
+        let mut out = String::new();
+        for arg in args {
+            out += &unwrap_runtime_value_serve(arg, scope)?.to_string().to_uppercase();
+        }
+        Ok(RuntimeValueServe::Owned(Box::new(StrLiteral{content: out})))
+    })?;
+
+    ion.register("pow", pow_native())?;
+    ion.register("repeat", repeat_native())?;
+
+    ion.eval_str(r#"| answer <asg> <structure: numeric> base * 2;"#)?;
+    println!("answer = {}", ion.get_global("answer")?);
+    println!("{}", ion.eval_str(r#"shout("hello from ", "ion");"#)?);
+    println!("{}", ion.eval_str("pow(2, 10);")?);
+    println!("{}", ion.eval_str(r#"repeat("ab", 3);"#)?);
+    for script in ["pow(2, 2.5);", "pow(2);", r#"repeat("ab", 1000);"#] {
+        if let Err(err) = ion.eval_str(script) {
+            println!("{}", err);
+        }
+    }
+
+    // Globals keep their declared type when the host reassigns them
+    ion.set_global("base", Box::new(NumericVal{value: 1.0}))?;
+    if let Err(err) = ion.set_global("base", Box::new(StrLiteral{content: "one".to_string()})) {
+        println!("{}", err);
+    }
+    Ok(())
+}
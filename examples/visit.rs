@@ -0,0 +1,81 @@
+// Walks a parsed script twice: a `Visitor` that gathers metrics, then a `VisitorMut` that renames a variable.
+// Run with `cargo run --example visit`.
+use std::collections::BTreeMap;
+
+use ion::ast::visit::{walk_call_expr, walk_fn_struct};
+use ion::ast::visit_mut::walk_var_declaration;
+use ion::ast::{CallExpr, Expr, FnStruct, Identifier, VarDeclaration, Visitor, VisitorMut};
+use ion::error::IonError;
+use ion::{parser, Interpreter};
+
+const SCRIPT: &str = r#"
+| total <asg> <structure: numeric> 0;
+| add <asg> <structure: function> fn (n: numeric) @ numeric {
+    return total + n;
+};
+for i in 0..10 {
+    total <asg> add(i);
+}
+log(total, "\n");
+"#;
+
+#[derive(Default)]
+struct Metrics {
+    calls: BTreeMap<String, usize>,
+    functions: usize,
+    max_depth: usize,
+    depth: usize,
+}
+
+impl Visitor for Metrics {
+    fn visit_call_expr(&mut self, node: &CallExpr) {
+        if let Expr::Identifier(callee) = node.call_to.as_ref() {
+            *self.calls.entry(callee.symbol.clone()).or_default() += 1;
+        }
+        walk_call_expr(self, node);
+    }
+
+    fn visit_fn_struct(&mut self, node: &FnStruct) {
+        self.functions += 1;
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        walk_fn_struct(self, node);
+        self.depth -= 1;
+    }
+}
+
+struct Rename<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl VisitorMut for Rename<'_> {
+    // Declarations hold their name as a plain string rather than an `Identifier` node
+    fn visit_var_declaration(&mut self, node: &mut VarDeclaration) {
+        if node.identifier == self.from {
+            node.identifier = self.to.to_string();
+        }
+        walk_var_declaration(self, node);
+    }
+
+    fn visit_identifier(&mut self, node: &mut Identifier) {
+        if node.symbol == self.from {
+            node.symbol = self.to.to_string();
+        }
+    }
+}
+
+fn main() -> Result<(), IonError> {
+    let mut program = parser::prod_ast(SCRIPT.to_string())?;
+
+    let mut metrics = Metrics::default();
+    program.accept(&mut metrics);
+    println!("calls: {:?}", metrics.calls);
+    println!("functions: {} (max nesting {})", metrics.functions, metrics.max_depth);
+
+    program.accept_mut(&mut Rename{from: "total", to: "sum"});
+    let mut interpreter = Interpreter::new();
+    interpreter.eval(program)?;
+    println!("sum = {}", interpreter.get_global("sum")?);
+    Ok(())
+}
@@ -1,26 +1,73 @@
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput, ExprPath};
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, ExprPath, Field, Fields, FnArg, Ident, ItemFn, Pat};
 
+// Plain AST node: carries the source span it was parsed from
 #[proc_macro_attribute]
-pub fn Stmt(attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn Node(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    let node_type = parse_macro_input!(attr as ExprPath);
+    TokenStream::from(node(input))
+}
+
+fn node(mut input: DeriveInput) -> proc_macro2::TokenStream {
+    let mut compared = vec![];
+    if let Data::Struct(DataStruct { fields: Fields::Named(ref mut fields), .. }) = input.data {
+        compared = fields.named.iter().map(|field| field.ident.clone()).collect();
+        fields.named.push(Field::parse_named.parse2(quote! { pub span: Span }).unwrap());
+    }
+
+    // Visitor hooks: `BinExpr` is handed to `visit_bin_expr`
     let name = &input.ident;
+    let visit = format_ident!("visit_{}", snake_case(&name.to_string()));
 
-    let expanded = quote! {
+    quote! {
         #[derive(Debug, Clone)]
         #input
 
-        impl Stmt for #name {
-            fn kind(&self) -> NodeType {
-                #node_type
+        impl #name {
+            pub fn accept<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+                visitor.#visit(self)
             }
-            fn as_any(&self) -> &dyn Any {
-                self
+            pub fn accept_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+                visitor.#visit(self)
             }
-            fn clone_box(&self) -> Box<dyn Stmt> {
-                Box::new(self.clone())
+        }
+
+        // Nodes compare by shape: the same code written somewhere else is still equal
+        impl PartialEq for #name {
+            fn eq(&self, other: &Self) -> bool {
+                true #(&& self.#compared == other.#compared)*
+            }
+        }
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+// `#[Stmt(VarDecl)]` makes the node a `Stmt::VarDecl`
+#[proc_macro_attribute]
+pub fn Stmt(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let variant = parse_macro_input!(attr as Ident);
+    let name = input.ident.clone();
+    let node = node(input);
+
+    let expanded = quote! {
+        #node
+
+        impl From<#name> for Stmt {
+            fn from(node: #name) -> Stmt {
+                Stmt::#variant(node)
             }
         }
     };
@@ -28,31 +75,31 @@ pub fn Stmt(attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+// `#[Expr(Binary)]` makes the node an `Expr::Binary`, usable wherever a statement is expected too
 #[proc_macro_attribute]
 pub fn Expr(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let node_type = attr.clone();       
-    let item_clone = item.clone();      
-
-    let stmt_expanded = Stmt(node_type, item_clone);
-    let stmt_expanded2: proc_macro2::TokenStream = stmt_expanded.into();
-
     let input = parse_macro_input!(item as DeriveInput);
-    let name = &input.ident;
+    let variant = parse_macro_input!(attr as Ident);
+    let name = input.ident.clone();
+    let node = node(input);
 
-    let expr_expanded = quote! {
-        impl Expr for #name {
-            fn clone_box_expr(&self) -> Box<dyn Expr> {
-                Box::new(self.clone())
+    let expanded = quote! {
+        #node
+
+        impl From<#name> for Expr {
+            fn from(node: #name) -> Expr {
+                Expr::#variant(node)
             }
         }
-    };
 
-    let combined = quote! {
-        #stmt_expanded2
-        #expr_expanded
+        impl From<#name> for Stmt {
+            fn from(node: #name) -> Stmt {
+                Stmt::Expr(Expr::#variant(node))
+            }
+        }
     };
 
-    TokenStream::from(combined)
+    TokenStream::from(expanded)
 }
 
 
@@ -81,3 +128,67 @@ pub fn RuntimeValue(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+// Turns a typed Rust function into an Ion native: `#[ion_native] fn pow(base: f64, exp: i64) -> f64`
+// keeps `pow` callable from Rust and adds `pow_native()`, building a `NativeFnValue` that checks arity,
+// converts each argument through `FromValue` and the result through `NativeReturn`.
+#[proc_macro_attribute]
+pub fn ion_native(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let func = parse_macro_input!(item as ItemFn);
+    match native_wrapper(&func) {
+        Ok(wrapper) => TokenStream::from(quote! {
+            #func
+            #wrapper
+        }),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn native_wrapper(func: &ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &func.sig;
+    if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+        return Err(syn::Error::new_spanned(sig, "#[ion_native] functions can't be generic or async"));
+    }
+
+    let mut params = vec![];
+    let mut types = vec![];
+    for input in &sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            return Err(syn::Error::new_spanned(input, "#[ion_native] functions can't take `self`"));
+        };
+        let Pat::Ident(ident) = arg.pat.as_ref() else {
+            return Err(syn::Error::new_spanned(&arg.pat, "#[ion_native] parameters must be plain identifiers"));
+        };
+        params.push(ident.ident.clone());
+        types.push(arg.ty.as_ref().clone());
+    }
+
+    let vis = &func.vis;
+    let name = &sig.ident;
+    let name_str = name.to_string();
+    let param_strs = params.iter().map(|param| param.to_string());
+    let arity = params.len();
+    let native = format_ident!("{}_native", name);
+
+    Ok(quote! {
+        #vis fn #native() -> ::ion::values::NativeFnValue {
+            #[allow(unused_variables, unused_mut)]
+            fn call(
+                args: Vec<::ion::interpreter::RuntimeValueServe>,
+                scope: &::std::rc::Rc<::std::cell::RefCell<::ion::scopes::Scope>>,
+            ) -> Result<::ion::interpreter::RuntimeValueServe, ::ion::error::IonError> {
+                if args.len() != #arity {
+                    return Err(::ion::error::IonError::runtime(format!(
+                        "Function [ {} ] expected {} argument(s), found {}", #name_str, #arity, args.len()
+                    )));
+                }
+                let mut args = args.into_iter();
+                #(
+                    let #params: #types = ::ion::native::from_arg(args.next().unwrap(), scope, #name_str, #param_strs)?;
+                )*
+                ::ion::native::NativeReturn::into_result(#name(#(#params),*))
+            }
+            ::ion::values::NativeFnValue{call: Box::new(call as fn(_, &_) -> _)}
+        }
+    })
+}
@@ -1,173 +1,476 @@
+use std::fmt;
+
 use ion_macros::*;
-use std::any::Any;
-use std::fmt::Debug;
-use num_traits::Num;
 
+use crate::error::Span;
 use crate::lexer::{Attr, Flags};
 
-#[derive(PartialEq)]
-pub enum NodeType{
-
-    //Stmt
-
-    Program,
-    VarDecl,
-    VarAsg,
-
-    //Expr
-
-    NumericLiteralNode,
-    String,
-    Identifier,
-    BinOp,
-    Nil,
-    Bool,
+pub mod printer;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stmt {
+    VarDecl(VarDeclaration),
+    VarAsg(VarAsg),
+    Return(Return),
+    If(IfStmt),
+    While(WhileStmt),
+    For(ForStmt),
+    Break(Break),
+    Continue(Continue),
+    Expr(Expr),
+}
 
-    Object,
-    MemberExpr,
-    Array,
-    ArrMemberExpr,
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Numeric(NumericLiteral),
+    Str(Str),
+    Identifier(Identifier),
+    Nil(Nil),
+    Bool(Bool),
+
+    Binary(BinExpr),
+    Unary(UnaryExpr),
+    Range(RangeExpr),
+
+    Object(Object),
+    ObjectLiteral(ObjectLiteral),
+    Array(Array),
+    ArrayLiteral(ArrayLiteral),
+    Member(MemberExpr),
+    ArrMember(ArrMemberExpr),
+
+    FnStruct(FnStruct),
+    Call(CallExpr),
+}
 
-    Property,
-    ObjectLiteral,
-    PropertyLiteral,
-    ArrayLiteral,
+impl Stmt {
+    pub fn span(&self) -> Span {
+        match self {
+            Stmt::VarDecl(node) => node.span,
+            Stmt::VarAsg(node) => node.span,
+            Stmt::Return(node) => node.span,
+            Stmt::If(node) => node.span,
+            Stmt::While(node) => node.span,
+            Stmt::For(node) => node.span,
+            Stmt::Break(node) => node.span,
+            Stmt::Continue(node) => node.span,
+            Stmt::Expr(expr) => expr.span(),
+        }
+    }
+}
 
-    FnStruct,
-    Param,
-    CallExpr
+impl Expr {
+    pub fn span(&self) -> Span {
+        match self {
+            Expr::Numeric(node) => node.span,
+            Expr::Str(node) => node.span,
+            Expr::Identifier(node) => node.span,
+            Expr::Nil(node) => node.span,
+            Expr::Bool(node) => node.span,
+            Expr::Binary(node) => node.span,
+            Expr::Unary(node) => node.span,
+            Expr::Range(node) => node.span,
+            Expr::Object(node) => node.span,
+            Expr::ObjectLiteral(node) => node.span,
+            Expr::Array(node) => node.span,
+            Expr::ArrayLiteral(node) => node.span,
+            Expr::Member(node) => node.span,
+            Expr::ArrMember(node) => node.span,
+            Expr::FnStruct(node) => node.span,
+            Expr::Call(node) => node.span,
+        }
+    }
 }
 
-#[Stmt(NodeType::Program)] 
+#[Node]
 pub struct Program{
-    pub body: Vec<Box<dyn Stmt>>
+    pub body: Vec<Stmt>
 }
 
-#[Stmt(NodeType::VarDecl)] 
+#[Stmt(VarDecl)]
 pub struct VarDeclaration{
     pub identifier: String,
     pub flags: Vec<Flags>,
-    pub value: Box<dyn Expr>
+    pub value: Box<Expr>
 }
 
-#[Stmt(NodeType::VarAsg)] 
+#[Stmt(VarAsg)]
 pub struct VarAsg{
-    pub lhs: Box<dyn Expr>,
-    pub rhs: Box<dyn Expr>
+    pub lhs: Box<Expr>,
+    pub rhs: Box<Expr>
+}
+
+
+#[Stmt(Return)]
+pub struct Return{
+    pub value: Box<Expr>
+}
+
+#[Stmt(If)]
+pub struct IfStmt{
+    pub condition: Box<Expr>,
+    pub body: Vec<Stmt>,
+    pub alternate: Option<Vec<Stmt>>,
 }
 
+#[Stmt(While)]
+pub struct WhileStmt{
+    pub condition: Box<Expr>,
+    pub body: Vec<Stmt>,
+}
 
-#[Expr(NodeType::Bool)]
+#[Stmt(For)]
+pub struct ForStmt{
+    pub item: String,
+    pub iterable: Box<Expr>,
+    pub body: Vec<Stmt>,
+}
+
+#[Stmt(Break)]
+pub struct Break{}
+
+#[Stmt(Continue)]
+pub struct Continue{}
+
+#[Expr(Bool)]
 pub struct Bool{
     pub value: bool
 }
 
-#[Expr(NodeType::BinOp)]
+#[Expr(Binary)]
 pub struct BinExpr {
-    pub left: Box<dyn Expr>,
-    pub right: Box<dyn Expr>,
+    pub left: Box<Expr>,
+    pub right: Box<Expr>,
+    pub operator: String,
+}
+
+#[Expr(Unary)]
+pub struct UnaryExpr {
+    pub operand: Box<Expr>,
     pub operator: String,
 }
 
-#[Expr(NodeType::Identifier)]
+#[Expr(Range)]
+pub struct RangeExpr {
+    pub start: Box<Expr>,
+    pub end: Box<Expr>,
+}
+
+#[Expr(Identifier)]
 pub struct Identifier{
     pub symbol: String,
 }
 
-#[Expr(NodeType::String)]
+#[Expr(Str)]
 pub struct Str{
     pub content: String,
 }
 
-#[Expr(NodeType::NumericLiteralNode)]
-pub struct NumericLiteral<T: Num + Debug = f64>{
-    pub value: T,
+#[Expr(Numeric)]
+pub struct NumericLiteral{
+    pub value: Number,
+}
+
+// A number as written: whole numbers are kept exact, anything with a fraction is a float
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number{
+    Int(i128),
+    Float(f64),
+}
+
+impl Number{
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(n) => n as f64,
+            Number::Float(n) => n,
+        }
+    }
+
+    pub fn is_negative(self) -> bool {
+        match self {
+            Number::Int(n) => n < 0,
+            Number::Float(n) => n.is_sign_negative(),
+        }
+    }
+}
+
+impl fmt::Display for Number{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Int(n) => write!(f, "{}", n),
+            // `7.0` stays recognisably a float
+            Number::Float(n) if n.fract() == 0.0 => write!(f, "{}.0", n),
+            Number::Float(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl std::ops::Neg for Number{
+    type Output = Number;
+
+    fn neg(self) -> Number {
+        match self {
+            Number::Int(n) => Number::Int(-n),
+            Number::Float(n) => Number::Float(-n),
+        }
+    }
 }
 
-#[Expr(NodeType::Nil)]
+#[Expr(Nil)]
 pub struct Nil{}
 
-#[Expr(NodeType::Property)]
+#[Node]
 pub struct Property{
     pub key: String,
     pub value: Attr,
 }
 
-#[Expr(NodeType::Object)]
+#[Expr(Object)]
 pub struct Object{
     pub properties: Vec<Property>,
 }
 
-#[Expr(NodeType::Array)]
+#[Expr(Array)]
 pub struct Array{
     pub attr: Attr,
     pub complex_attr: Option<String>,
     pub length: usize,
 }
 
-#[Expr(NodeType::ArrayLiteral)]
+#[Expr(ArrayLiteral)]
 pub struct ArrayLiteral{
-    pub entries: Vec<Box<dyn Expr>>,
+    pub entries: Vec<Expr>,
 }
 
-#[Expr(NodeType::PropertyLiteral)]
+#[Node]
 pub struct PropertyLiteral{
     pub key: String,
-    pub value: Box<dyn Expr>,
+    pub value: Expr,
 }
 
-#[Expr(NodeType::ObjectLiteral)]
+#[Expr(ObjectLiteral)]
 pub struct ObjectLiteral{
     pub properties: Vec<PropertyLiteral>,
 }
 
-#[Expr(NodeType::MemberExpr)]
+#[Expr(Member)]
 pub struct MemberExpr{
-    pub obj: Box<dyn Expr>,
-    pub prop: Box<dyn Expr>,
+    pub obj: Box<Expr>,
+    pub prop: Identifier,
 }
 
-#[Expr(NodeType::ArrMemberExpr)]
+#[Expr(ArrMember)]
 pub struct ArrMemberExpr{
-    pub arr: Box<dyn Expr>,
-    pub index: Box<dyn Expr>,
+    pub arr: Box<Expr>,
+    pub index: Box<Expr>,
 }
 
-#[Expr(NodeType::Param)]
+#[Node]
 pub struct Param{
     pub param: String,
     pub param_type: Attr,
 }
 
-#[Expr(NodeType::FnStruct)]
+#[Expr(FnStruct)]
 pub struct FnStruct{
     pub params: Vec<Param>,
     pub ret_type: Attr,
+    pub body: Vec<Stmt>,
 }
 
-#[Expr(NodeType::CallExpr)]
+#[Expr(Call)]
 pub struct CallExpr{
-    pub args: Vec<Box<dyn Expr>>,
-    pub call_to: Box<dyn Expr>,
+    pub args: Vec<Expr>,
+    pub call_to: Box<Expr>,
 }
 
-impl Clone for Box<dyn Stmt> {
-    fn clone(&self) -> Box<dyn Stmt> {
-        self.clone_box()
-    }
-}
-impl Clone for Box<dyn Expr> {
-    fn clone(&self) -> Box<dyn Expr> {
-        self.clone_box_expr()
-    }
+pub use visit::Visitor;
+pub use visit_mut::VisitorMut;
+
+// Generates a visitor trait plus its `walk_*` functions; `Visitor` borrows the tree, `VisitorMut`
+// borrows it mutably. Every `visit_*` defaults to walking the node's children, so an implementor
+// overrides only the nodes it cares about and calls the matching `walk_*` to keep descending.
+macro_rules! visitor {
+    ($Visitor:ident, $accept:ident $(, $mut:ident)?) => {
+        pub trait $Visitor {
+            fn visit_program(&mut self, node: &$($mut)? Program) { walk_program(self, node) }
+            fn visit_stmt(&mut self, stmt: &$($mut)? Stmt) { walk_stmt(self, stmt) }
+            fn visit_expr(&mut self, expr: &$($mut)? Expr) { walk_expr(self, expr) }
+
+            fn visit_var_declaration(&mut self, node: &$($mut)? VarDeclaration) { walk_var_declaration(self, node) }
+            fn visit_var_asg(&mut self, node: &$($mut)? VarAsg) { walk_var_asg(self, node) }
+            fn visit_return(&mut self, node: &$($mut)? Return) { walk_return(self, node) }
+            fn visit_if_stmt(&mut self, node: &$($mut)? IfStmt) { walk_if_stmt(self, node) }
+            fn visit_while_stmt(&mut self, node: &$($mut)? WhileStmt) { walk_while_stmt(self, node) }
+            fn visit_for_stmt(&mut self, node: &$($mut)? ForStmt) { walk_for_stmt(self, node) }
+            fn visit_break(&mut self, _node: &$($mut)? Break) {}
+            fn visit_continue(&mut self, _node: &$($mut)? Continue) {}
+
+            fn visit_numeric_literal(&mut self, _node: &$($mut)? NumericLiteral) {}
+            fn visit_str(&mut self, _node: &$($mut)? Str) {}
+            fn visit_identifier(&mut self, _node: &$($mut)? Identifier) {}
+            fn visit_nil(&mut self, _node: &$($mut)? Nil) {}
+            fn visit_bool(&mut self, _node: &$($mut)? Bool) {}
+            fn visit_bin_expr(&mut self, node: &$($mut)? BinExpr) { walk_bin_expr(self, node) }
+            fn visit_unary_expr(&mut self, node: &$($mut)? UnaryExpr) { walk_unary_expr(self, node) }
+            fn visit_range_expr(&mut self, node: &$($mut)? RangeExpr) { walk_range_expr(self, node) }
+            fn visit_object(&mut self, node: &$($mut)? Object) { walk_object(self, node) }
+            fn visit_property(&mut self, _node: &$($mut)? Property) {}
+            fn visit_object_literal(&mut self, node: &$($mut)? ObjectLiteral) { walk_object_literal(self, node) }
+            fn visit_property_literal(&mut self, node: &$($mut)? PropertyLiteral) { walk_property_literal(self, node) }
+            fn visit_array(&mut self, _node: &$($mut)? Array) {}
+            fn visit_array_literal(&mut self, node: &$($mut)? ArrayLiteral) { walk_array_literal(self, node) }
+            fn visit_member_expr(&mut self, node: &$($mut)? MemberExpr) { walk_member_expr(self, node) }
+            fn visit_arr_member_expr(&mut self, node: &$($mut)? ArrMemberExpr) { walk_arr_member_expr(self, node) }
+            fn visit_fn_struct(&mut self, node: &$($mut)? FnStruct) { walk_fn_struct(self, node) }
+            fn visit_param(&mut self, _node: &$($mut)? Param) {}
+            fn visit_call_expr(&mut self, node: &$($mut)? CallExpr) { walk_call_expr(self, node) }
+        }
+
+        pub fn walk_program<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? Program) {
+            walk_block(visitor, &$($mut)? node.body);
+        }
+
+        pub fn walk_block<V: $Visitor + ?Sized>(visitor: &mut V, body: &$($mut)? [Stmt]) {
+            for stmt in body {
+                visitor.visit_stmt(stmt);
+            }
+        }
+
+        pub fn walk_stmt<V: $Visitor + ?Sized>(visitor: &mut V, stmt: &$($mut)? Stmt) {
+            match stmt {
+                Stmt::VarDecl(node) => node.$accept(visitor),
+                Stmt::VarAsg(node) => node.$accept(visitor),
+                Stmt::Return(node) => node.$accept(visitor),
+                Stmt::If(node) => node.$accept(visitor),
+                Stmt::While(node) => node.$accept(visitor),
+                Stmt::For(node) => node.$accept(visitor),
+                Stmt::Break(node) => node.$accept(visitor),
+                Stmt::Continue(node) => node.$accept(visitor),
+                Stmt::Expr(expr) => visitor.visit_expr(expr),
+            }
+        }
+
+        pub fn walk_expr<V: $Visitor + ?Sized>(visitor: &mut V, expr: &$($mut)? Expr) {
+            match expr {
+                Expr::Numeric(node) => node.$accept(visitor),
+                Expr::Str(node) => node.$accept(visitor),
+                Expr::Identifier(node) => node.$accept(visitor),
+                Expr::Nil(node) => node.$accept(visitor),
+                Expr::Bool(node) => node.$accept(visitor),
+                Expr::Binary(node) => node.$accept(visitor),
+                Expr::Unary(node) => node.$accept(visitor),
+                Expr::Range(node) => node.$accept(visitor),
+                Expr::Object(node) => node.$accept(visitor),
+                Expr::ObjectLiteral(node) => node.$accept(visitor),
+                Expr::Array(node) => node.$accept(visitor),
+                Expr::ArrayLiteral(node) => node.$accept(visitor),
+                Expr::Member(node) => node.$accept(visitor),
+                Expr::ArrMember(node) => node.$accept(visitor),
+                Expr::FnStruct(node) => node.$accept(visitor),
+                Expr::Call(node) => node.$accept(visitor),
+            }
+        }
+
+        pub fn walk_var_declaration<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? VarDeclaration) {
+            visitor.visit_expr(&$($mut)? node.value);
+        }
+
+        pub fn walk_var_asg<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? VarAsg) {
+            visitor.visit_expr(&$($mut)? node.lhs);
+            visitor.visit_expr(&$($mut)? node.rhs);
+        }
+
+        pub fn walk_return<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? Return) {
+            visitor.visit_expr(&$($mut)? node.value);
+        }
+
+        pub fn walk_if_stmt<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? IfStmt) {
+            visitor.visit_expr(&$($mut)? node.condition);
+            walk_block(visitor, &$($mut)? node.body);
+            if let Some(alternate) = &$($mut)? node.alternate {
+                walk_block(visitor, alternate);
+            }
+        }
+
+        pub fn walk_while_stmt<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? WhileStmt) {
+            visitor.visit_expr(&$($mut)? node.condition);
+            walk_block(visitor, &$($mut)? node.body);
+        }
+
+        pub fn walk_for_stmt<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? ForStmt) {
+            visitor.visit_expr(&$($mut)? node.iterable);
+            walk_block(visitor, &$($mut)? node.body);
+        }
+
+        pub fn walk_bin_expr<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? BinExpr) {
+            visitor.visit_expr(&$($mut)? node.left);
+            visitor.visit_expr(&$($mut)? node.right);
+        }
+
+        pub fn walk_unary_expr<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? UnaryExpr) {
+            visitor.visit_expr(&$($mut)? node.operand);
+        }
+
+        pub fn walk_range_expr<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? RangeExpr) {
+            visitor.visit_expr(&$($mut)? node.start);
+            visitor.visit_expr(&$($mut)? node.end);
+        }
+
+        pub fn walk_object<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? Object) {
+            for property in &$($mut)? node.properties {
+                property.$accept(visitor);
+            }
+        }
+
+        pub fn walk_object_literal<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? ObjectLiteral) {
+            for property in &$($mut)? node.properties {
+                property.$accept(visitor);
+            }
+        }
+
+        pub fn walk_property_literal<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? PropertyLiteral) {
+            visitor.visit_expr(&$($mut)? node.value);
+        }
+
+        pub fn walk_array_literal<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? ArrayLiteral) {
+            for entry in &$($mut)? node.entries {
+                visitor.visit_expr(entry);
+            }
+        }
+
+        pub fn walk_member_expr<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? MemberExpr) {
+            visitor.visit_expr(&$($mut)? node.obj);
+            node.prop.$accept(visitor);
+        }
+
+        pub fn walk_arr_member_expr<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? ArrMemberExpr) {
+            visitor.visit_expr(&$($mut)? node.arr);
+            visitor.visit_expr(&$($mut)? node.index);
+        }
+
+        pub fn walk_fn_struct<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? FnStruct) {
+            for param in &$($mut)? node.params {
+                param.$accept(visitor);
+            }
+            walk_block(visitor, &$($mut)? node.body);
+        }
+
+        pub fn walk_call_expr<V: $Visitor + ?Sized>(visitor: &mut V, node: &$($mut)? CallExpr) {
+            visitor.visit_expr(&$($mut)? node.call_to);
+            for arg in &$($mut)? node.args {
+                visitor.visit_expr(arg);
+            }
+        }
+    };
 }
 
-pub trait Stmt: Debug + Any{
-    fn kind(&self) -> NodeType;
-    fn as_any(&self) -> &dyn Any;
-    fn clone_box(&self) -> Box<dyn Stmt>;
-}
-pub trait Expr: Stmt{
-    fn clone_box_expr(&self) -> Box<dyn Expr>;
+pub mod visit {
+    use super::*;
+    visitor!(Visitor, accept);
 }
 
+pub mod visit_mut {
+    use super::*;
+    visitor!(VisitorMut, accept_mut, mut);
+}
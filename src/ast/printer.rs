@@ -0,0 +1,460 @@
+use std::collections::VecDeque;
+
+use super::*;
+use crate::lexer::Comment;
+
+// Turns an AST back into canonical Ion source: one statement per line, four space indents,
+// flags in the order they were written and only the parentheses the parser needs, so that
+// `prod_ast(print(&program))` yields `program` again (spans aside).
+pub fn print(program: &Program) -> String {
+    let mut printer = Printer::default();
+    for stmt in &program.body {
+        printer.stmt(stmt);
+    }
+    printer.out
+}
+
+// Like `print`, but keeps the source's comments and blank lines (at most one in a row) around
+// the statements they were written next to. Comments inside an expression move below its statement.
+pub fn print_with_comments(program: &Program, comments: Vec<Comment>) -> String {
+    let mut printer = Printer{comments: comments.into(), layout: true, ..Printer::default()};
+    for stmt in &program.body {
+        printer.stmt(stmt);
+    }
+    printer.comments_before(isize::MAX);
+    printer.out
+}
+
+pub fn print_stmt(stmt: &Stmt) -> String {
+    let mut printer = Printer::default();
+    printer.stmt(stmt);
+    printer.out.trim_end().to_string()
+}
+
+pub fn print_expr(expr: &Expr) -> String {
+    let mut printer = Printer::default();
+    printer.expr(expr, Prec::Top);
+    printer.out
+}
+
+pub fn print_flag(flag: &Flags) -> String {
+    match flag {
+        Flags::Assign_f => "<asg>".to_string(),
+        Flags::Const_f => "<const>".to_string(),
+        Flags::Struct_f(attr) => format!("<structure: {}>", attr_name(attr)),
+        Flags::Complex_f(attr) => format!("<complex: {}>", attr_name(attr)),
+        Flags::Overflow_f(policy) => format!("<overflow: {}>", policy.name()),
+    }
+}
+
+pub fn attr_name(attr: &Attr) -> &str {
+    match attr {
+        Attr::Numeric => "numeric",
+        Attr::String => "string",
+        Attr::Bool => "bool",
+        Attr::Object => "object",
+        Attr::Array => "array",
+        Attr::Function => "function",
+        Attr::ComplexKind => "complex",
+        Attr::Complex(name) => name,
+        Attr::Fixed(kind) => kind.name(),
+    }
+}
+
+// Binding strength, mirroring the parser's descent: each level only parses operands above it
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Prec {
+    Top,     // object / array literals and structs, fn structs
+    Range,
+    Or,
+    And,
+    Compare,
+    Additive,
+    Multiplicative,
+    Prim,    // literals, identifiers, unary operators, parenthesised expressions
+    Postfix, // member access and calls
+}
+
+fn prec(expr: &Expr) -> Prec {
+    match expr {
+        Expr::Object(_) | Expr::ObjectLiteral(_) | Expr::Array(_) | Expr::ArrayLiteral(_) | Expr::FnStruct(_) => Prec::Top,
+        Expr::Range(_) => Prec::Range,
+        Expr::Binary(node) if negated(node).is_some() => Prec::Prim,
+        Expr::Binary(node) => binary_prec(&node.operator),
+        Expr::Numeric(node) if node.value.is_negative() => Prec::Prim,
+        Expr::Member(_) | Expr::ArrMember(_) | Expr::Call(_) => Prec::Postfix,
+        _ => Prec::Prim,
+    }
+}
+
+fn binary_prec(operator: &str) -> Prec {
+    match operator {
+        "||" => Prec::Or,
+        "&&" => Prec::And,
+        "+" | "-" => Prec::Additive,
+        "*" | "/" | "%" => Prec::Multiplicative,
+        _ => Prec::Compare,
+    }
+}
+
+// The parser reads `-x` as `-1 * x`; such products are printed back as a negation
+fn negated(node: &BinExpr) -> Option<&Expr> {
+    match node.left.as_ref() {
+        Expr::Numeric(NumericLiteral{value, ..}) if *value == Number::Int(-1) && node.operator == "*" => Some(&node.right),
+        _ => None,
+    }
+}
+
+// A bare primary: the only thing the parser accepts right of `*`, `/` and `%`
+fn is_prim(expr: &Expr) -> bool {
+    prec(expr) == Prec::Prim
+}
+
+// What member access and calls may be chained onto without parentheses. A call can't follow
+// a member of a call (`f().g()`), the parser stops after the member.
+fn is_postfix_base(expr: &Expr, calling: bool) -> bool {
+    match expr {
+        Expr::Unary(_) => false,
+        Expr::Member(_) | Expr::ArrMember(_) => !calling || !ends_in_call(expr),
+        _ => prec(expr) >= Prec::Prim,
+    }
+}
+
+fn ends_in_call(expr: &Expr) -> bool {
+    match expr {
+        Expr::Member(node) => ends_in_call(&node.obj),
+        Expr::ArrMember(node) => ends_in_call(&node.arr),
+        Expr::Call(_) => true,
+        _ => false,
+    }
+}
+
+// `"a" + b` switches the parser to string concatenation, which only continues with `+`
+fn starts_concat(expr: &Expr) -> bool {
+    match expr {
+        Expr::Binary(node) if node.operator == "+" => matches!(node.left.as_ref(), Expr::Str(_)) || starts_concat(&node.left),
+        _ => false,
+    }
+}
+
+#[derive(Default)]
+struct Printer {
+    out: String,
+    indent: usize,
+    // Only set by `print_with_comments`: the comments not printed yet and the last source line printed
+    layout: bool,
+    comments: VecDeque<Comment>,
+    last_line: isize,
+}
+
+impl Printer {
+    fn line_start(&mut self) {
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        let span = stmt.span();
+        if self.layout {
+            self.comments_before(span.start.0);
+            self.blank_line(span.start.0);
+        }
+        self.line_start();
+        self.stmt_inline(stmt);
+        self.out.push('\n');
+        if self.layout {
+            self.trailing_comments(span.end.0);
+            self.last_line = span.end.0;
+        }
+    }
+
+    // Comments left over from a statement's lines (the statement may now take fewer of them): the
+    // first one trails it, any others follow on their own lines
+    fn trailing_comments(&mut self, end: isize) {
+        let mut first = true;
+        while let Some(comment) = self.comments.pop_front_if(|comment| comment.span.start.0 <= end) {
+            if first {
+                self.out.pop();
+                self.out.push(' ');
+            } else {
+                self.line_start();
+            }
+            self.out.push_str(&comment.text);
+            self.out.push('\n');
+            first = false;
+        }
+    }
+
+    // Prints the pending comments that start above `line`, each on its own line
+    fn comments_before(&mut self, line: isize) {
+        while let Some(comment) = self.comments.pop_front_if(|comment| comment.span.start.0 < line) {
+            self.blank_line(comment.span.start.0);
+            self.line_start();
+            self.out.push_str(&comment.text);
+            self.out.push('\n');
+            self.last_line = comment.span.end.0;
+        }
+    }
+
+    // Keeps one blank line where the source had any, except at the top of a file or block
+    fn blank_line(&mut self, line: isize) {
+        if self.last_line > 0 && line > self.last_line + 1 {
+            self.out.push('\n');
+        }
+    }
+
+    // Everything after the indent of the statement's first line
+    fn stmt_inline(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::VarDecl(node) => {
+                self.out.push_str("| ");
+                self.out.push_str(&node.identifier);
+                for flag in &node.flags {
+                    self.out.push(' ');
+                    self.out.push_str(&print_flag(flag));
+                }
+                if node.flags.contains(&Flags::Assign_f) {
+                    self.out.push(' ');
+                    self.expr(&node.value, Prec::Top);
+                }
+                self.out.push(';');
+            },
+            Stmt::VarAsg(node) => {
+                self.expr(&node.lhs, Prec::Top);
+                self.out.push_str(" <asg> ");
+                self.expr(&node.rhs, Prec::Top);
+                self.out.push(';');
+            },
+            Stmt::Return(node) => {
+                self.out.push_str("return");
+                if !matches!(node.value.as_ref(), Expr::Nil(_)) {
+                    self.out.push(' ');
+                    self.expr(&node.value, Prec::Top);
+                }
+                self.out.push(';');
+            },
+            Stmt::If(node) => self.if_stmt(node),
+            Stmt::While(node) => {
+                self.out.push_str("while ");
+                self.expr(&node.condition, Prec::Top);
+                self.out.push(' ');
+                self.block(&node.body, node.span.start.0, node.span.end.0);
+            },
+            Stmt::For(node) => {
+                self.out.push_str("for ");
+                self.out.push_str(&node.item);
+                self.out.push_str(" in ");
+                self.expr(&node.iterable, Prec::Top);
+                self.out.push(' ');
+                self.block(&node.body, node.span.start.0, node.span.end.0);
+            },
+            Stmt::Break(_) => self.out.push_str("break;"),
+            Stmt::Continue(_) => self.out.push_str("continue;"),
+            Stmt::Expr(expr) => {
+                self.expr(expr, Prec::Top);
+                self.out.push(';');
+            },
+        }
+    }
+
+    fn if_stmt(&mut self, node: &IfStmt) {
+        self.out.push_str("if ");
+        self.expr(&node.condition, Prec::Top);
+        self.out.push(' ');
+        // Comments between the body and an `else` end up in the else block
+        self.block(&node.body, node.span.start.0, if node.alternate.is_some() { 0 } else { node.span.end.0 });
+        match node.alternate.as_deref() {
+            Some([Stmt::If(elif)]) => {
+                self.out.push_str(" else ");
+                self.if_stmt(elif);
+            },
+            Some(alternate) => {
+                self.out.push_str(" else ");
+                self.block(alternate, 0, node.span.end.0);
+            },
+            None => {},
+        }
+    }
+
+    // `open` and `end` are the lines of the braces: a comment on the opening line stays there and
+    // comments above the closing one still belong inside the block
+    fn block(&mut self, body: &[Stmt], open: isize, end: isize) {
+        let has_comments = self.layout && self.comments.front().is_some_and(|comment| comment.span.start.0 < end);
+        if body.is_empty() && !has_comments {
+            self.out.push_str("{}");
+            return;
+        }
+        self.out.push('{');
+        if let Some(comment) = self.comments.pop_front_if(|comment| comment.span.start.0 == open) {
+            self.out.push(' ');
+            self.out.push_str(&comment.text);
+        }
+        self.out.push('\n');
+        self.last_line = 0;
+        self.indent += 1;
+        for stmt in body {
+            self.stmt(stmt);
+        }
+        if self.layout {
+            self.comments_before(end);
+        }
+        self.indent -= 1;
+        self.line_start();
+        self.out.push('}');
+    }
+
+    // Prints `expr` where the parser expects something binding at least as tightly as `min`
+    fn expr(&mut self, expr: &Expr, min: Prec) {
+        if prec(expr) < min {
+            self.out.push('(');
+            self.expr(expr, Prec::Top);
+            self.out.push(')');
+            return;
+        }
+
+        match expr {
+            Expr::Numeric(node) if node.value.is_negative() => self.out.push_str(&format!("(-{})", -node.value)),
+            Expr::Numeric(node) => self.out.push_str(&node.value.to_string()),
+            Expr::Str(node) => {
+                self.out.push('"');
+                self.out.push_str(&node.content);
+                self.out.push('"');
+            },
+            Expr::Identifier(node) => self.out.push_str(&node.symbol),
+            Expr::Nil(_) => self.out.push_str("nil"),
+            Expr::Bool(node) => self.out.push_str(if node.value { "true" } else { "false" }),
+            Expr::Binary(node) => self.binary(node),
+            Expr::Unary(node) => {
+                self.out.push_str(&node.operator);
+                self.expr(&node.operand, Prec::Prim);
+            },
+            Expr::Range(node) => {
+                self.expr(&node.start, Prec::Or);
+                self.out.push_str("..");
+                self.expr(&node.end, Prec::Or);
+            },
+            Expr::Object(node) => {
+                self.out.push_str("obj {");
+                for property in &node.properties {
+                    self.out.push_str(&format!(" {}: {};", property.key, attr_name(&property.value)));
+                }
+                self.out.push_str(if node.properties.is_empty() { "}" } else { " }" });
+            },
+            Expr::ObjectLiteral(node) => {
+                self.out.push('{');
+                for property in &node.properties {
+                    self.out.push(' ');
+                    self.out.push_str(&property.key);
+                    self.out.push_str(": ");
+                    self.expr(&property.value, Prec::Top);
+                    self.out.push(';');
+                }
+                self.out.push_str(if node.properties.is_empty() { "}" } else { " }" });
+            },
+            Expr::Array(node) => {
+                let complex = node.complex_attr.as_deref().unwrap_or("nil");
+                self.out.push_str(&format!("arr [{}; {}; {};]", attr_name(&node.attr), complex, node.length));
+            },
+            Expr::ArrayLiteral(node) => {
+                self.out.push('[');
+                self.list(&node.entries);
+                self.out.push(']');
+            },
+            Expr::Member(node) => {
+                self.postfix_base(&node.obj, false);
+                self.out.push('.');
+                self.out.push_str(&node.prop.symbol);
+            },
+            Expr::ArrMember(node) => {
+                self.postfix_base(&node.arr, false);
+                self.out.push('[');
+                self.expr(&node.index, Prec::Top);
+                self.out.push(']');
+            },
+            Expr::FnStruct(node) => {
+                self.out.push_str("fn (");
+                for (i, param) in node.params.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.out.push_str(&format!("{}: {}", param.param, attr_name(&param.param_type)));
+                }
+                self.out.push_str(&format!(") @ {} ", attr_name(&node.ret_type)));
+                self.block(&node.body, node.span.start.0, node.span.end.0);
+            },
+            Expr::Call(node) => {
+                self.postfix_base(&node.call_to, true);
+                self.out.push('(');
+                self.list(&node.args);
+                self.out.push(')');
+            },
+        }
+    }
+
+    fn binary(&mut self, node: &BinExpr) {
+        if let Some(operand) = negated(node) {
+            self.out.push_str("(-");
+            self.prim(operand);
+            self.out.push(')');
+            return;
+        }
+
+        let level = binary_prec(&node.operator);
+        if node.operator == "-" && starts_concat(&node.left) {
+            self.out.push('(');
+            self.expr(&node.left, Prec::Top);
+            self.out.push(')');
+        } else {
+            self.expr(&node.left, level);
+        }
+        self.out.push_str(&format!(" {} ", node.operator));
+        if level == Prec::Multiplicative {
+            self.prim(&node.right);
+        } else {
+            // Left associative: an operand of the same level on the right needs parentheses
+            self.expr(&node.right, next(level));
+        }
+    }
+
+    fn prim(&mut self, expr: &Expr) {
+        if is_prim(expr) {
+            self.expr(expr, Prec::Prim);
+        } else {
+            self.out.push('(');
+            self.expr(expr, Prec::Top);
+            self.out.push(')');
+        }
+    }
+
+    fn postfix_base(&mut self, expr: &Expr, calling: bool) {
+        if is_postfix_base(expr, calling) {
+            self.expr(expr, Prec::Prim);
+        } else {
+            self.out.push('(');
+            self.expr(expr, Prec::Top);
+            self.out.push(')');
+        }
+    }
+
+    fn list(&mut self, entries: &[Expr]) {
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            self.expr(entry, Prec::Top);
+        }
+    }
+}
+
+fn next(level: Prec) -> Prec {
+    match level {
+        Prec::Top => Prec::Range,
+        Prec::Range => Prec::Or,
+        Prec::Or => Prec::And,
+        Prec::And => Prec::Compare,
+        Prec::Compare => Prec::Additive,
+        Prec::Additive => Prec::Multiplicative,
+        _ => Prec::Prim,
+    }
+}
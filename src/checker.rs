@@ -0,0 +1,748 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ast::{BinExpr, CallExpr, Expr, FnStruct, ForStmt, Number, Program, Stmt, VarAsg, VarDeclaration};
+use crate::diagnostic::suggest;
+use crate::error::{IonError, Span};
+use crate::fixed::fixed_value;
+use crate::interpreter::{number_value, operand_mismatch, RuntimeValueServe};
+use crate::lexer::{Attr, Flags, Overflow, ATTR_NAMES};
+use crate::scopes::{Parent, Scope};
+use crate::values::{ArrayVal, FuncStructVal, NativeFnValue, ObjectVal, RuntimeValue};
+
+// Type checks a program before it runs: every `<structure>`/`<complex>` flag is resolved and
+// every declaration, assignment, call, return and condition is checked against the types the
+// expressions involved are known to have. The rules are the interpreter's (`nil` fits any
+// `<structure>` but a named complex, `anonymous` fits anything, ...), so a script that checks
+// cleanly only fails at runtime on values (division by zero, indexes out of bounds, ...).
+// Whatever can't be known up front (function parameters of `complex`, `anonymous` values,
+// names a function body refers to before they are declared) is left for the interpreter.
+pub fn check(program: &Program, globals: &Scope) -> Vec<IonError> {
+    let mut checker = Checker{scopes: vec![HashMap::new()], errors: vec![], functions: vec![], loops: 0};
+    checker.declare_globals(globals);
+    checker.scopes.push(HashMap::new());
+    checker.block(&program.body);
+    checker.errors
+}
+
+// What the checker knows about a value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ty {
+    Unknown,
+    Nil,
+    Numeric,
+    String,
+    Bool,
+    Range,
+    Function(Option<Signature>),
+    // `obj { .. }` / `arr [..]` structs, with their definition when it is known
+    ObjStruct(Option<Vec<(String, Attr)>>),
+    ArrStruct(Option<ArrDef>),
+    // Object / array literals
+    Object(Vec<(String, Ty)>),
+    Array(Vec<Ty>),
+    // A value checked against `<complex: name>`
+    Named(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub params: Vec<Attr>,
+    pub ret: Attr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrDef {
+    pub attr: Attr,
+    pub complex: Option<String>,
+    pub length: usize,
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ty::Unknown => write!(f, "unknown"),
+            Ty::Nil => write!(f, "nil"),
+            Ty::Numeric => write!(f, "numeric"),
+            Ty::String => write!(f, "string"),
+            Ty::Bool => write!(f, "bool"),
+            Ty::Range => write!(f, "range"),
+            Ty::Function(_) => write!(f, "function"),
+            Ty::ObjStruct(_) => write!(f, "object"),
+            Ty::ArrStruct(_) => write!(f, "array"),
+            Ty::Object(fields) => {
+                write!(f, "{{")?;
+                for (key, ty) in fields {
+                    write!(f, " {}: {};", key, ty)?;
+                }
+                write!(f, " }}")
+            },
+            Ty::Array(entries) => {
+                let entries: Vec<String> = entries.iter().map(|ty| ty.to_string()).collect();
+                write!(f, "[{}]", entries.join(", "))
+            },
+            Ty::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+// The type a value checked against `attr` (a parameter, a struct field) is known to have
+fn attr_ty(attr: &Attr) -> Ty {
+    match attr {
+        Attr::Numeric | Attr::Fixed(_) => Ty::Numeric,
+        Attr::String => Ty::String,
+        Attr::Bool => Ty::Bool,
+        Attr::Object => Ty::ObjStruct(None),
+        Attr::Array => Ty::ArrStruct(None),
+        Attr::Function => Ty::Function(None),
+        Attr::Complex(name) if name != "anonymous" => Ty::Named(name.clone()),
+        Attr::Complex(_) | Attr::ComplexKind => Ty::Unknown,
+    }
+}
+
+// A variable's type as its flags declare it
+fn declared_ty(flags: &[Flags]) -> Ty {
+    match structure(flags) {
+        Some(Attr::ComplexKind) => match complex(flags) {
+            Some(name) if name != "anonymous" => Ty::Named(name.to_string()),
+            _ => Ty::Unknown,
+        },
+        Some(attr) => attr_ty(attr),
+        None => Ty::Unknown,
+    }
+}
+
+fn structure(flags: &[Flags]) -> Option<&Attr> {
+    flags.iter().find_map(|flag| match flag {
+        Flags::Struct_f(attr) => Some(attr),
+        _ => None,
+    })
+}
+
+fn complex(flags: &[Flags]) -> Option<&str> {
+    flags.iter().find_map(|flag| match flag {
+        Flags::Complex_f(Attr::Complex(name)) => Some(name.as_str()),
+        _ => None,
+    })
+}
+
+// A declared type narrowed by the value it holds: a function's signature, a struct's definition
+fn refine(declared: Ty, value: &Ty) -> Ty {
+    match (&declared, value) {
+        (Ty::Function(None), Ty::Function(Some(_))) | (Ty::ObjStruct(None), Ty::ObjStruct(Some(_))) | (Ty::ArrStruct(None), Ty::ArrStruct(Some(_))) => value.clone(),
+        _ => declared,
+    }
+}
+
+// The flags `infer_flags` gives a value of this type, if it is known
+fn inferred_flags(ty: &Ty) -> Option<Vec<Flags>> {
+    let attr = match ty {
+        Ty::Unknown => return None,
+        Ty::Numeric => Attr::Numeric,
+        Ty::String => Attr::String,
+        Ty::Bool => Attr::Bool,
+        Ty::ObjStruct(_) => Attr::Object,
+        Ty::ArrStruct(_) => Attr::Array,
+        Ty::Function(_) => Attr::Function,
+        _ => return Some(vec![Flags::Struct_f(Attr::ComplexKind), Flags::Complex_f(Attr::Complex("anonymous".to_string()))]),
+    };
+    Some(vec![Flags::Struct_f(attr)])
+}
+
+fn runtime_ty(value: &dyn RuntimeValue) -> Option<Ty> {
+    let any = value.as_any();
+    if any.is::<NativeFnValue>() {
+        return Some(Ty::Function(None));
+    }
+    if let Some(func) = any.downcast_ref::<FuncStructVal>() {
+        let params = func.parameters.iter().map(|param| param.param_type.clone()).collect();
+        return Some(Ty::Function(Some(Signature{params, ret: func.return_type.clone()})));
+    }
+    if let Some(obj) = any.downcast_ref::<ObjectVal>() {
+        let mut fields: Vec<(String, Attr)> = obj.properties.iter().map(|(key, attr)| (key.clone(), attr.clone())).collect();
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+        return Some(Ty::ObjStruct(Some(fields)));
+    }
+    if let Some(arr) = any.downcast_ref::<ArrayVal>() {
+        let complex = arr.complex.as_ref().map(|ident| ident.symbol.clone());
+        return Some(Ty::ArrStruct(Some(ArrDef{attr: arr.attr.clone(), complex, length: arr.length})));
+    }
+    None
+}
+
+struct Var {
+    ty: Ty,
+    flags: Vec<Flags>,
+}
+
+// How a `<complex: name>` resolves
+enum Complex {
+    Object(Vec<(String, Attr)>),
+    Array(ArrDef),
+    // Declared, but not as a struct whose definition is known here
+    Opaque,
+    Missing,
+}
+
+struct Checker {
+    scopes: Vec<HashMap<String, Var>>,
+    errors: Vec<IonError>,
+    // Return types of the function bodies being checked, innermost last
+    functions: Vec<Attr>,
+    loops: usize,
+}
+
+impl Checker {
+    fn declare_globals(&mut self, scope: &Scope) {
+        if let Parent::Scope(parent) = &scope.parent {
+            self.declare_globals(&parent.borrow());
+        }
+        for (name, entry) in &scope.variables {
+            // Natives are declared without a `<structure>`, so the value has the final say
+            let ty = match &entry.value {
+                RuntimeValueServe::Owned(value) => runtime_ty(value.as_ref()).map(|ty| refine(declared_ty(&entry.flags), &ty)),
+                RuntimeValueServe::Ref(_) => None,
+            };
+            let ty = ty.unwrap_or_else(|| declared_ty(&entry.flags));
+            self.scopes[0].insert(name.clone(), Var{ty, flags: entry.flags.clone()});
+        }
+    }
+
+    fn error(&mut self, err: IonError, span: Span) {
+        self.errors.push(err.or_span(span));
+    }
+
+    // Names are only certain to be missing outside function bodies; a body may use names declared after it
+    fn in_function(&self) -> bool {
+        !self.functions.is_empty()
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Var> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn undefined(&self, name: &str) -> IonError {
+        let err = IonError::name(format!("Variable [{}] doesn't exist in the current scope!", name));
+        let names: Vec<&str> = self.scopes.iter().flat_map(|scope| scope.keys().map(|key| key.as_str())).collect();
+        match suggest(name, names) {
+            Some(similar) => err.with_help(format!("a variable with a similar name exists: `{}`", similar)),
+            None => err,
+        }
+    }
+
+    fn declare(&mut self, name: &str, ty: Ty, flags: Vec<Flags>) {
+        self.scopes.last_mut().unwrap().insert(name.to_string(), Var{ty, flags});
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Checker)) {
+        self.scopes.push(HashMap::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn block(&mut self, body: &[Stmt]) {
+        for stmt in body {
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::VarDecl(node) => self.var_decl(node),
+            Stmt::VarAsg(node) => self.var_asg(node),
+            Stmt::Return(node) => {
+                let ty = self.expr(&node.value);
+                match self.functions.last().cloned() {
+                    Some(ret) => {
+                        if let Err(err) = self.check_attr(&ty, &ret) {
+                            self.error(err, node.span);
+                        }
+                    },
+                    None => self.error(IonError::runtime("[ return ] can only be used inside of a function body"), node.span),
+                }
+            },
+            Stmt::If(node) => {
+                self.condition(&node.condition, "if");
+                self.scoped(|checker| checker.block(&node.body));
+                if let Some(alternate) = &node.alternate {
+                    self.scoped(|checker| checker.block(alternate));
+                }
+            },
+            Stmt::While(node) => {
+                self.condition(&node.condition, "while");
+                self.loops += 1;
+                self.scoped(|checker| checker.block(&node.body));
+                self.loops -= 1;
+            },
+            Stmt::For(node) => self.for_stmt(node),
+            Stmt::Break(node) if self.loops == 0 => self.error(IonError::runtime("[ break ] can only be used inside of a loop"), node.span),
+            Stmt::Continue(node) if self.loops == 0 => self.error(IonError::runtime("[ continue ] can only be used inside of a loop"), node.span),
+            Stmt::Break(_) | Stmt::Continue(_) => {},
+            Stmt::Expr(expr) => {
+                self.expr(expr);
+            },
+        }
+    }
+
+    fn var_decl(&mut self, node: &VarDeclaration) {
+        let value = self.expr(&node.value);
+        if node.identifier == "_" {
+            return self.error(IonError::name("Token (_) cannot be used as an identifier."), node.span);
+        }
+        if self.scopes.last().unwrap().contains_key(&node.identifier) {
+            let err = IonError::name(format!("Variable [{}] is already defined in the current scope", node.identifier));
+            return self.error(err, node.span);
+        }
+
+        let mut flags = node.flags.clone();
+        let mut ty = None;
+        if structure(&flags).is_none() {
+            if complex(&flags).is_some() {
+                flags.push(Flags::Struct_f(Attr::ComplexKind));
+            } else if value == Ty::Nil {
+                let err = IonError::runtime(format!("Missing flag <structure> on [ {} ]", node.identifier))
+                    .with_help("the type of a variable declared as nil can't be inferred; add e.g. `<structure: numeric>`");
+                self.error(err, node.span);
+            } else if let Some(inferred) = inferred_flags(&value) {
+                // Literals are inferred as `anonymous`, but their shape is still known here
+                if matches!(inferred[..], [Flags::Struct_f(Attr::ComplexKind), ..]) {
+                    ty = Some(value.clone());
+                }
+                flags.extend(inferred);
+            }
+        }
+
+        if let Some(attr) = structure(&flags) {
+            if let Err(err) = self.check_value(&value, attr, complex(&flags)).and_then(|_| check_literal(&node.value, attr)) {
+                self.error(err, node.span);
+            }
+        }
+        let ty = ty.unwrap_or_else(|| refine(declared_ty(&flags), &value));
+        self.declare(&node.identifier, ty, flags);
+    }
+
+    fn var_asg(&mut self, node: &VarAsg) {
+        let value = self.expr(&node.rhs);
+        match node.lhs.as_ref() {
+            Expr::Identifier(ident) => {
+                let Some(var) = self.lookup(&ident.symbol) else {
+                    if !self.in_function() {
+                        self.error(self.undefined(&ident.symbol), ident.span);
+                    }
+                    return;
+                };
+                let flags = var.flags.clone();
+                // Only declarations whose type is left to the interpreter to infer have no `<structure>` here
+                let result = match structure(&flags) {
+                    Some(attr) => self.check_value(&value, attr, complex(&flags)).and_then(|_| check_literal(&node.rhs, attr)),
+                    None => Ok(()),
+                };
+                if let Err(err) = result {
+                    return self.error(err, node.span);
+                }
+                if flags.contains(&Flags::Const_f) {
+                    let err = IonError::runtime(format!("Cannot reassign variable [{}] marked with flag: <const>", ident.symbol));
+                    return self.error(err, node.span);
+                }
+                // A function or struct reassigned from an inner block may or may not have been replaced
+                let ty = if self.scopes.last().unwrap().contains_key(&ident.symbol) {
+                    refine(declared_ty(&flags), &value)
+                } else {
+                    declared_ty(&flags)
+                };
+                for scope in self.scopes.iter_mut().rev() {
+                    if let Some(var) = scope.get_mut(&ident.symbol) {
+                        var.ty = ty;
+                        break;
+                    }
+                }
+            },
+            Expr::Member(member) => {
+                let obj = self.expr(&member.obj);
+                let Some(field) = self.member_ty(&obj, &member.prop.symbol, member.span) else {
+                    return;
+                };
+                if let Err(err) = self.check_field(&value, &field) {
+                    self.error(err, node.span);
+                }
+            },
+            _ => self.error(IonError::runtime("Can only assign values to an identifier or an object property"), node.span),
+        }
+    }
+
+    fn for_stmt(&mut self, node: &ForStmt) {
+        let item = match self.expr(&node.iterable) {
+            Ty::Range => Ty::Numeric,
+            Ty::Array(entries) => common_ty(&entries),
+            Ty::Named(name) => match self.resolve_complex(&name) {
+                Complex::Array(def) => match def.attr {
+                    Attr::ComplexKind => def.complex.map(Ty::Named).unwrap_or(Ty::Unknown),
+                    attr => attr_ty(&attr),
+                },
+                Complex::Object(_) => {
+                    let err = IonError::typing(format!("[ for ] can only iterate over an array or a range | Found: {}", name));
+                    self.error(err, node.iterable.span());
+                    Ty::Unknown
+                },
+                _ => Ty::Unknown,
+            },
+            Ty::Unknown => Ty::Unknown,
+            other => {
+                let err = IonError::typing(format!("[ for ] can only iterate over an array or a range | Found: {}", other));
+                self.error(err, node.iterable.span());
+                Ty::Unknown
+            },
+        };
+        self.loops += 1;
+        self.scoped(|checker| {
+            checker.declare(&node.item, item, vec![]);
+            checker.block(&node.body);
+        });
+        self.loops -= 1;
+    }
+
+    fn condition(&mut self, condition: &Expr, keyword: &str) {
+        let ty = self.expr(condition);
+        if !matches!(ty, Ty::Bool | Ty::Unknown) {
+            let err = IonError::typing(format!("Condition of [ {} ] must be of type bool | Found: {}", keyword, ty));
+            self.error(err, condition.span());
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Ty {
+        match expr {
+            Expr::Numeric(_) => Ty::Numeric,
+            Expr::Str(_) => Ty::String,
+            Expr::Bool(_) => Ty::Bool,
+            Expr::Nil(_) => Ty::Nil,
+            Expr::Identifier(ident) => match self.lookup(&ident.symbol) {
+                Some(var) => var.ty.clone(),
+                None => {
+                    if !self.in_function() {
+                        self.error(self.undefined(&ident.symbol), ident.span);
+                    }
+                    Ty::Unknown
+                },
+            },
+            Expr::Binary(node) => self.binary(node),
+            Expr::Unary(node) => {
+                self.bool_operand(&node.operand, &node.operator);
+                Ty::Bool
+            },
+            Expr::Range(node) => {
+                for bound in [&node.start, &node.end] {
+                    let ty = self.expr(bound);
+                    if !matches!(ty, Ty::Numeric | Ty::Unknown) {
+                        self.error(IonError::typing(format!("Range bounds must be numeric | Found: {}", ty)), bound.span());
+                    }
+                }
+                Ty::Range
+            },
+            Expr::Object(node) => Ty::ObjStruct(Some(node.properties.iter().map(|prop| (prop.key.clone(), prop.value.clone())).collect())),
+            Expr::Array(node) => Ty::ArrStruct(Some(ArrDef{attr: node.attr.clone(), complex: node.complex_attr.clone(), length: node.length})),
+            Expr::ObjectLiteral(node) => Ty::Object(node.properties.iter().map(|prop| (prop.key.clone(), self.expr(&prop.value))).collect()),
+            Expr::ArrayLiteral(node) => Ty::Array(node.entries.iter().map(|entry| self.expr(entry)).collect()),
+            Expr::Member(node) => {
+                let obj = self.expr(&node.obj);
+                self.member_ty(&obj, &node.prop.symbol, node.span).unwrap_or(Ty::Unknown)
+            },
+            Expr::ArrMember(node) => {
+                let arr = self.expr(&node.arr);
+                let index = self.expr(&node.index);
+                if !matches!(index, Ty::Numeric | Ty::Unknown) {
+                    self.error(IonError::typing(format!("Expected integer-compatible value for op | Found: {}", index)), node.index.span());
+                }
+                self.index_ty(arr, node.span)
+            },
+            Expr::FnStruct(node) => self.fn_struct(node),
+            Expr::Call(node) => self.call(node),
+        }
+    }
+
+    fn binary(&mut self, node: &BinExpr) -> Ty {
+        if node.operator == "&&" || node.operator == "||" {
+            self.bool_operand(&node.left, &node.operator);
+            self.bool_operand(&node.right, &node.operator);
+            return Ty::Bool;
+        }
+        let left = self.expr(&node.left);
+        let right = self.expr(&node.right);
+        match node.operator.as_str() {
+            "==" | "!=" => Ty::Bool,
+            "<" | "<=" | ">" | ">=" => {
+                if !matches!(left, Ty::Numeric | Ty::Unknown) || !matches!(right, Ty::Numeric | Ty::Unknown) {
+                    let err = IonError::typing(format!("Operator [ {} ] can only compare numeric values | Found: {} {} {}", node.operator, left, node.operator, right));
+                    self.error(err, node.span);
+                }
+                Ty::Bool
+            },
+            _ => match (&left, &right) {
+                (Ty::Numeric, Ty::Numeric) => Ty::Numeric,
+                (Ty::String, Ty::String) => Ty::String,
+                (Ty::Unknown, _) | (_, Ty::Unknown) => Ty::Unknown,
+                // As in the interpreter: a nil operand has no value to compute with yet, so the result is nil
+                (Ty::Nil, _) | (_, Ty::Nil) => Ty::Nil,
+                _ => {
+                    self.error(operand_mismatch(&node.operator, &left, &right), node.span);
+                    Ty::Unknown
+                },
+            },
+        }
+    }
+
+    fn bool_operand(&mut self, operand: &Expr, operator: &str) {
+        let ty = self.expr(operand);
+        if !matches!(ty, Ty::Bool | Ty::Unknown) {
+            let err = IonError::typing(format!("Operator [ {} ] expects operands of type bool | Found: {}", operator, ty));
+            self.error(err, operand.span());
+        }
+    }
+
+    // The type of `obj.prop`, or None (with the error reported) when it can't be accessed
+    fn member_ty(&mut self, obj: &Ty, prop: &str, span: Span) -> Option<Ty> {
+        let fields: Vec<(String, Ty)> = match obj {
+            Ty::Object(fields) => fields.clone(),
+            Ty::Named(name) => match self.resolve_complex(name) {
+                Complex::Object(fields) => fields.iter().map(|(key, attr)| (key.clone(), attr_ty(attr))).collect(),
+                Complex::Array(_) => {
+                    let err = IonError::typing(format!("Property access can only be executed on an object | Found: {}", name));
+                    self.error(err, span);
+                    return None;
+                },
+                _ => return Some(Ty::Unknown),
+            },
+            Ty::Unknown => return Some(Ty::Unknown),
+            other => {
+                let err = IonError::typing(format!("Property access can only be executed on an object | Found: {}", other));
+                self.error(err, span);
+                return None;
+            },
+        };
+        match fields.into_iter().find(|(key, _)| key == prop) {
+            Some((_, ty)) => Some(ty),
+            None => {
+                self.error(IonError::name(format!("Property '{}' not found", prop)), span);
+                None
+            },
+        }
+    }
+
+    fn index_ty(&mut self, arr: Ty, span: Span) -> Ty {
+        match arr {
+            Ty::Array(entries) => common_ty(&entries),
+            Ty::Named(name) => match self.resolve_complex(&name) {
+                Complex::Array(def) => match def.attr {
+                    Attr::ComplexKind => def.complex.map(Ty::Named).unwrap_or(Ty::Unknown),
+                    attr => attr_ty(&attr),
+                },
+                Complex::Object(_) => {
+                    self.error(IonError::typing(format!("Array indexing can only be executed on an array | Found: {}", name)), span);
+                    Ty::Unknown
+                },
+                _ => Ty::Unknown,
+            },
+            Ty::Unknown => Ty::Unknown,
+            other => {
+                self.error(IonError::typing(format!("Array indexing can only be executed on an array | Found: {}", other)), span);
+                Ty::Unknown
+            },
+        }
+    }
+
+    fn fn_struct(&mut self, node: &FnStruct) -> Ty {
+        let loops = std::mem::take(&mut self.loops);
+        self.functions.push(node.ret_type.clone());
+        self.scoped(|checker| {
+            for param in &node.params {
+                let flags = match &param.param_type {
+                    Attr::Complex(_) => vec![Flags::Struct_f(Attr::ComplexKind), Flags::Complex_f(param.param_type.clone())],
+                    attr => vec![Flags::Struct_f(attr.clone())],
+                };
+                checker.declare(&param.param, attr_ty(&param.param_type), flags);
+            }
+            checker.block(&node.body);
+            // The last statement's value is handed back when the body doesn't return
+            if let Some(Stmt::Expr(last)) = node.body.last() {
+                let ty = checker.expr_quiet(last);
+                if let Err(err) = checker.check_attr(&ty, &node.ret_type) {
+                    checker.error(err, last.span());
+                }
+            }
+        });
+        self.functions.pop();
+        self.loops = loops;
+
+        let params = node.params.iter().map(|param| param.param_type.clone()).collect();
+        Ty::Function(Some(Signature{params, ret: node.ret_type.clone()}))
+    }
+
+    // Infers an expression that was already checked, without reporting its errors twice
+    fn expr_quiet(&mut self, expr: &Expr) -> Ty {
+        let reported = self.errors.len();
+        let ty = self.expr(expr);
+        self.errors.truncate(reported);
+        ty
+    }
+
+    fn call(&mut self, node: &CallExpr) -> Ty {
+        let args: Vec<Ty> = node.args.iter().map(|arg| self.expr(arg)).collect();
+        match self.expr(&node.call_to) {
+            Ty::Function(Some(sig)) => {
+                if sig.params.len() != args.len() {
+                    let name = crate::ast::printer::print_expr(&node.call_to);
+                    let err = IonError::runtime(format!("Function [ {} ] expected {} argument(s), found {}", name, sig.params.len(), args.len()));
+                    self.error(err, node.span);
+                    return attr_ty(&sig.ret);
+                }
+                for ((attr, arg), expr) in sig.params.iter().zip(&args).zip(&node.args) {
+                    if let Err(err) = self.check_attr(arg, attr).and_then(|_| check_literal(expr, attr)) {
+                        self.error(err, expr.span());
+                    }
+                }
+                match sig.ret {
+                    // A `complex` return type is checked against nothing at runtime either
+                    Attr::ComplexKind => Ty::Unknown,
+                    ret => attr_ty(&ret),
+                }
+            },
+            Ty::Function(None) | Ty::Unknown => Ty::Unknown,
+            other => {
+                let name = crate::ast::printer::print_expr(&node.call_to);
+                self.error(IonError::typing(format!("Expression [ {} ] is not callable | Found: {}", name, other)), node.call_to.span());
+                Ty::Unknown
+            },
+        }
+    }
+
+    // `value` checked against a parameter, return or struct field type, where a bare
+    // `Complex(name)` stands for `<structure: complex> <complex: name>`
+    fn check_attr(&self, value: &Ty, attr: &Attr) -> Result<(), IonError> {
+        match attr {
+            Attr::Complex(name) => self.check_value(value, &Attr::ComplexKind, Some(name)),
+            attr => self.check_value(value, attr, None),
+        }
+    }
+
+    // `value` assigned to an object property of type `field`
+    fn check_field(&self, value: &Ty, field: &Ty) -> Result<(), IonError> {
+        match field {
+            Ty::Named(name) => self.check_value(value, &Attr::ComplexKind, Some(name)),
+            Ty::Numeric => self.check_value(value, &Attr::Numeric, None),
+            Ty::String => self.check_value(value, &Attr::String, None),
+            Ty::Bool => self.check_value(value, &Attr::Bool, None),
+            Ty::ObjStruct(_) => self.check_value(value, &Attr::Object, None),
+            Ty::ArrStruct(_) => self.check_value(value, &Attr::Array, None),
+            Ty::Function(_) => self.check_value(value, &Attr::Function, None),
+            _ => Ok(()),
+        }
+    }
+
+    // `value` checked against `<structure: attr>` (and `<complex: complex>`), as `static_type_check` does at runtime
+    fn check_value(&self, value: &Ty, attr: &Attr, complex: Option<&str>) -> Result<(), IonError> {
+        let fits = match attr {
+            Attr::Numeric | Attr::Fixed(_) => matches!(value, Ty::Numeric),
+            Attr::String => matches!(value, Ty::String),
+            Attr::Bool => matches!(value, Ty::Bool),
+            Attr::Object => matches!(value, Ty::ObjStruct(_)),
+            Attr::Array => matches!(value, Ty::ArrStruct(_)),
+            Attr::Function => matches!(value, Ty::Function(_)),
+            Attr::ComplexKind => {
+                let name = complex.ok_or_else(|| IonError::typing("Complex Struct defined without complex flag specification"))?;
+                return self.check_complex(value, name);
+            },
+            Attr::Complex(name) => {
+                let err = IonError::typing(format!("Unknown <structure: {}>", name));
+                return Err(match suggest(name, ATTR_NAMES) {
+                    Some(attr) => err.with_help(format!("did you mean `<structure: {}>`?", attr)),
+                    None => err.with_help(format!("use `<structure: complex> <complex: {}>`", name)),
+                });
+            },
+        };
+        if fits || matches!(value, Ty::Nil | Ty::Unknown) {
+            return Ok(());
+        }
+        Err(IonError::typing(format!("Incorrect Type Assignement | Expected: {:?} | Found: {}", attr, value)))
+    }
+
+    fn resolve_complex(&self, name: &str) -> Complex {
+        match self.lookup(name).map(|var| &var.ty) {
+            Some(Ty::ObjStruct(Some(fields))) => Complex::Object(fields.clone()),
+            Some(Ty::ArrStruct(Some(def))) => Complex::Array(def.clone()),
+            Some(_) => Complex::Opaque,
+            None => Complex::Missing,
+        }
+    }
+
+    fn check_complex(&self, value: &Ty, name: &str) -> Result<(), IonError> {
+        if name == "anonymous" || *value == Ty::Unknown {
+            return Ok(());
+        }
+        match self.resolve_complex(name) {
+            Complex::Missing if self.in_function() => Ok(()),
+            Complex::Missing => Err(match suggest(name, ATTR_NAMES) {
+                Some(attr) => self.undefined(name).with_help(format!("did you mean `{}`?", attr)),
+                None => self.undefined(name),
+            }),
+            Complex::Opaque => match self.lookup(name).map(|var| &var.ty) {
+                Some(Ty::ObjStruct(_) | Ty::ArrStruct(_) | Ty::Unknown) => Ok(()),
+                _ => Err(IonError::typing(format!("Complex attr [ {} ] must refer to an object or array struct", name))),
+            },
+            Complex::Object(fields) => {
+                let shape = match value {
+                    Ty::Object(shape) => shape,
+                    // Values already checked against a struct of this name, or whose shape isn't known
+                    Ty::Named(_) => return Ok(()),
+                    other => return Err(IonError::typing(format!("Expected an object of type: {} | Found: {}", name, other))),
+                };
+                for (key, attr) in &fields {
+                    let Some((_, field)) = shape.iter().find(|(k, _)| k == key) else {
+                        return Err(IonError::typing(format!("Missing field [ {} ] on an object of type: {}", key, name)));
+                    };
+                    self.check_attr(field, attr)?;
+                }
+                if let Some((key, _)) = shape.iter().find(|(key, _)| !fields.iter().any(|(k, _)| k == key)) {
+                    return Err(IonError::typing(format!("Extra field [ {} ] on an object of type: {}", key, name)));
+                }
+                Ok(())
+            },
+            Complex::Array(def) => {
+                let entries = match value {
+                    Ty::Array(entries) => entries,
+                    Ty::Named(_) => return Ok(()),
+                    other => return Err(IonError::typing(format!("Expected an array of type: {} | Found: {}", name, other))),
+                };
+                if entries.len() != def.length {
+                    return Err(IonError::typing("The size of an array must be strictly equal to that of its complex."));
+                }
+                for entry in entries {
+                    self.check_value(entry, &def.attr, def.complex.as_deref())?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+// A number literal (`-3` being parsed as `-1 * 3`) checked against the range of a fixed-width type
+fn check_literal(expr: &Expr, attr: &Attr) -> Result<(), IonError> {
+    let Attr::Fixed(kind) = attr else {
+        return Ok(());
+    };
+    let value = match expr {
+        Expr::Numeric(lit) => lit.value,
+        Expr::Binary(bin) => match (bin.operator.as_str(), bin.left.as_ref(), bin.right.as_ref()) {
+            ("*", Expr::Numeric(sign), Expr::Numeric(lit)) if sign.value == Number::Int(-1) => -lit.value,
+            _ => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+    fixed_value(number_value(value)?.as_ref(), *kind, Overflow::default()).map(|_| ())
+}
+
+// The type every entry shares, if they all share one
+fn common_ty(entries: &[Ty]) -> Ty {
+    match entries.split_first() {
+        Some((first, rest)) if rest.iter().all(|ty| ty == first) => first.clone(),
+        _ => Ty::Unknown,
+    }
+}
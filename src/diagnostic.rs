@@ -0,0 +1,82 @@
+use crate::error::IonError;
+
+// Renders an error the way rustc does:
+//
+// type error[E0003]: Incorrect Type Assignement | Expected: Numeric | Found: hi
+//   --> main.io:1:1
+//    |
+//  1 | | x <asg> <structure: numeric> "hi";
+//    | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+//    |
+//    = help: ...
+pub fn render(err: &IonError, file: &str, source: &str) -> String {
+    let mut out = format!("{}[{}]: {}", err.kind.label(), err.kind.code(), err.msg);
+
+    let span = err.span;
+    let line = if span.is_known() { source.lines().nth(span.start.0 as usize - 1) } else { None };
+
+    let Some(line) = line else {
+        if span.is_known() {
+            out += &format!("\n  --> {}:{}:{}", file, span.start.0, span.start.1 + 1);
+        }
+        for note in &err.help {
+            out += &format!("\n  = help: {}", note);
+        }
+        return out;
+    };
+
+    let gutter = " ".repeat(span.start.0.to_string().len());
+    let line_len = line.chars().count() as isize;
+
+    // Multi-line spans are underlined up to the end of their first line
+    let start = span.start.1.clamp(0, line_len);
+    let end = if span.end.0 == span.start.0 { span.end.1.clamp(start, line_len.max(start + 1) - 1) } else { line_len - 1 };
+    let width = (end - start + 1).max(1) as usize;
+
+    out += &format!("\n{} --> {}:{}:{}", gutter, file, span.start.0, span.start.1 + 1);
+    out += &format!("\n{} |", gutter);
+    out += &format!("\n{} | {}", span.start.0, line);
+    out += &format!("\n{} | {}{}", gutter, " ".repeat(start as usize), "^".repeat(width));
+    if !err.help.is_empty() {
+        out += &format!("\n{} |", gutter);
+        for note in &err.help {
+            out += &format!("\n{} = help: {}", gutter, note);
+        }
+    }
+    out
+}
+
+// Closest candidate within a typo's reach of `name`, if any.
+pub fn suggest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let reach = name.chars().count() / 3;
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= reach)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// Levenshtein distance where swapping two adjacent chars counts as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
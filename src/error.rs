@@ -0,0 +1,112 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Span {
+    pub start: (isize, isize),
+    pub end: (isize, isize),
+}
+
+impl Span {
+    pub fn new(start: (isize, isize), end: (isize, isize)) -> Span {
+        Span { start, end }
+    }
+
+    pub fn at(loc: (isize, isize)) -> Span {
+        Span { start: loc, end: loc }
+    }
+
+    // Lines are 1-indexed (columns 0-indexed), so a zeroed span means "location not known".
+    pub fn is_known(&self) -> bool {
+        self.start.0 > 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorKind {
+    Lex,
+    Parse,
+    Type,
+    Runtime,
+    Name,
+}
+
+impl ErrorKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErrorKind::Lex => "lex error",
+            ErrorKind::Parse => "parse error",
+            ErrorKind::Type => "type error",
+            ErrorKind::Runtime => "runtime error",
+            ErrorKind::Name => "name error",
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Lex => "E0001",
+            ErrorKind::Parse => "E0002",
+            ErrorKind::Type => "E0003",
+            ErrorKind::Runtime => "E0004",
+            ErrorKind::Name => "E0005",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IonError {
+    pub kind: ErrorKind,
+    pub msg: String,
+    pub span: Span,
+    pub help: Vec<String>,
+}
+
+impl IonError {
+    pub fn new(kind: ErrorKind, msg: impl Into<String>, span: Span) -> IonError {
+        IonError { kind, msg: msg.into(), span, help: vec![] }
+    }
+
+    pub fn lex(msg: impl Into<String>, span: Span) -> IonError {
+        IonError::new(ErrorKind::Lex, msg, span)
+    }
+
+    pub fn parse(msg: impl Into<String>, span: Span) -> IonError {
+        IonError::new(ErrorKind::Parse, msg, span)
+    }
+
+    pub fn runtime(msg: impl Into<String>) -> IonError {
+        IonError::new(ErrorKind::Runtime, msg, Span::default())
+    }
+
+    pub fn typing(msg: impl Into<String>) -> IonError {
+        IonError::new(ErrorKind::Type, msg, Span::default())
+    }
+
+    pub fn name(msg: impl Into<String>) -> IonError {
+        IonError::new(ErrorKind::Name, msg, Span::default())
+    }
+
+    pub fn with_help(mut self, note: impl Into<String>) -> IonError {
+        self.help.push(note.into());
+        self
+    }
+
+    // Attaches a location to errors raised without one; known spans are kept.
+    pub fn or_span(mut self, span: Span) -> IonError {
+        if !self.span.is_known() {
+            self.span = span;
+        }
+        self
+    }
+}
+
+impl fmt::Display for IonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind.label(), self.msg)?;
+        if self.span.is_known() {
+            write!(f, " (At {}:{})", self.span.start.0, self.span.start.1 + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for IonError {}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use ion::diagnostic;
+use ion::formatter::format_source;
+
+use crate::test_runner::collect_scripts;
+
+// Formats every `.io` script under the given paths (default: the current directory) in place.
+// `--check` leaves the files alone and fails if any of them isn't formatted, for CI; `-` formats
+// stdin to stdout. Returns the process exit code.
+pub fn run(args: Vec<String>) -> i32 {
+    let check = args.iter().any(|arg| arg == "--check");
+    let mut roots: Vec<PathBuf> = args.iter().filter(|arg| *arg != "--check").map(PathBuf::from).collect();
+    if roots == [PathBuf::from("-")] {
+        return format_stdin(check);
+    }
+    if roots.is_empty() {
+        roots.push(PathBuf::from("."));
+    }
+
+    let mut scripts = vec![];
+    for root in &roots {
+        if let Err(e) = collect_scripts(root, &mut scripts) {
+            eprintln!("error: failed to read [ {} ]: {}", root.display(), e);
+            return 2;
+        }
+    }
+    scripts.sort();
+
+    let mut failed = false;
+    for script in &scripts {
+        failed |= !format_script(script, check);
+    }
+    if failed { 1 } else { 0 }
+}
+
+// Whether the script is (now) formatted
+fn format_script(script: &Path, check: bool) -> bool {
+    let source = match fs::read_to_string(script) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("error: failed to read [ {} ]: {}", script.display(), e);
+            return false;
+        },
+    };
+    let formatted = match format_source(&source) {
+        Ok(formatted) => formatted,
+        Err(err) => {
+            eprintln!("{}", diagnostic::render(&err, &script.display().to_string(), &source));
+            return false;
+        },
+    };
+    if formatted == source {
+        return true;
+    }
+    if check {
+        println!("Diff in {}:\n{}", script.display(), diff(&source, &formatted));
+        return false;
+    }
+    if let Err(e) = fs::write(script, formatted) {
+        eprintln!("error: failed to write [ {} ]: {}", script.display(), e);
+        return false;
+    }
+    true
+}
+
+fn format_stdin(check: bool) -> i32 {
+    let mut source = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut source) {
+        eprintln!("error: failed to read script from stdin: {}", e);
+        return 1;
+    }
+    match format_source(&source) {
+        Ok(formatted) if check => {
+            if formatted == source { 0 } else {
+                println!("Diff in <stdin>:\n{}", diff(&source, &formatted));
+                1
+            }
+        },
+        Ok(formatted) => {
+            print!("{}", formatted);
+            0
+        },
+        Err(err) => {
+            eprintln!("{}", diagnostic::render(&err, "<stdin>", &source));
+            1
+        },
+    }
+}
+
+// The first line that changes, before and after formatting
+fn diff(source: &str, formatted: &str) -> String {
+    let line = source.lines().zip(formatted.lines()).take_while(|(s, f)| s == f).count();
+    format!(
+        "  line {}\n  - {}\n  + {}",
+        line + 1,
+        source.lines().nth(line).unwrap_or("<end of file>"),
+        formatted.lines().nth(line).unwrap_or("<end of file>"),
+    )
+}
@@ -0,0 +1,45 @@
+use crate::ast::printer::print_with_comments;
+use crate::ast::{Program, VarDeclaration, VisitorMut};
+use crate::error::{IonError, Span};
+use crate::lexer::{tokenize_with_comments, Flags};
+use crate::parser::Parser;
+
+// Rewrites a script in the canonical layout `ion fmt` enforces: the printer's spacing and
+// indentation, flags in `Flags` declaration order (`<asg> <const> <structure: ..> <complex: ..>
+// <overflow: ..>`), comments and single blank lines kept where they were.
+pub fn format_source(source: &str) -> Result<String, IonError> {
+    let (tokens, comments) = tokenize_with_comments(source.to_string())?;
+    let mut program = Parser::new(tokens).parse_program()?;
+    sort_flags(&mut program);
+
+    let formatted = print_with_comments(&program, comments);
+    // Guards against a printer bug silently changing what the script means
+    let reparsed = crate::parser::prod_ast(formatted.clone())?;
+    if reparsed != program {
+        return Err(IonError::parse("Formatting would change the meaning of this script; leaving it untouched", Span::default()));
+    }
+    Ok(formatted)
+}
+
+pub fn sort_flags(program: &mut Program) {
+    FlagOrder.visit_program(program);
+}
+
+fn flag_rank(flag: &Flags) -> u8 {
+    match flag {
+        Flags::Assign_f => 0,
+        Flags::Const_f => 1,
+        Flags::Struct_f(_) => 2,
+        Flags::Complex_f(_) => 3,
+        Flags::Overflow_f(_) => 4,
+    }
+}
+
+struct FlagOrder;
+
+impl VisitorMut for FlagOrder {
+    fn visit_var_declaration(&mut self, node: &mut VarDeclaration) {
+        node.flags.sort_by_key(flag_rank);
+        crate::ast::visit_mut::walk_var_declaration(self, node);
+    }
+}
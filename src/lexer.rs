@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
-use crate::PRINT_;
+use crate::diagnostic::suggest;
+use crate::error::{IonError, Span};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token{
@@ -23,6 +24,14 @@ pub enum TokenType{
     Bool_false_t,
     arr_struct_k,
     obj_struct_k,
+    Return_k,
+    If_k,
+    Else_k,
+    While_k,
+    Break_k,
+    Continue_k,
+    For_k,
+    In_k,
 
     Flag(Flags),
 
@@ -35,6 +44,7 @@ pub enum TokenType{
     RightCurly,
     LeftCurly,
     Dot,
+    DotDot,
     RetType,
 }
 
@@ -44,6 +54,7 @@ pub enum Flags{
     Const_f,
     Struct_f(Attr),
     Complex_f(Attr),
+    Overflow_f(Overflow),
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -56,6 +67,62 @@ pub enum Attr{
     Complex(String),
     ComplexKind,
     Array,
+    Function,
+    Fixed(NumKind),
+}
+
+// The fixed-width numeric types `<structure: u8>` & co. declare
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum NumKind{
+    U8, U16, U32, U64,
+    I8, I16, I32, I64,
+    F32, F64,
+}
+
+pub const NUM_KINDS: [NumKind; 10] = [
+    NumKind::U8, NumKind::U16, NumKind::U32, NumKind::U64,
+    NumKind::I8, NumKind::I16, NumKind::I32, NumKind::I64,
+    NumKind::F32, NumKind::F64,
+];
+
+impl NumKind{
+    pub fn name(self) -> &'static str {
+        match self {
+            NumKind::U8 => "u8",
+            NumKind::U16 => "u16",
+            NumKind::U32 => "u32",
+            NumKind::U64 => "u64",
+            NumKind::I8 => "i8",
+            NumKind::I16 => "i16",
+            NumKind::I32 => "i32",
+            NumKind::I64 => "i64",
+            NumKind::F32 => "f32",
+            NumKind::F64 => "f64",
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, NumKind::F32 | NumKind::F64)
+    }
+}
+
+// What arithmetic on a fixed-width variable does with a result that doesn't fit: `<overflow: wrapping>`
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum Overflow{
+    #[default]
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+impl Overflow{
+    pub fn name(self) -> &'static str {
+        match self {
+            Overflow::Checked => "checked",
+            Overflow::Wrapping => "wrapping",
+            Overflow::Saturating => "saturating",
+        }
+    }
 }
 
 static keywords: Lazy<HashMap<&'static str, TokenType>> = Lazy::new(|| {
@@ -68,36 +135,53 @@ static keywords: Lazy<HashMap<&'static str, TokenType>> = Lazy::new(|| {
     map.insert("obj", TokenType::obj_struct_k);
     map.insert("arr", TokenType::arr_struct_k);
     map.insert("@", TokenType::RetType);
+    map.insert("return", TokenType::Return_k);
+    map.insert("if", TokenType::If_k);
+    map.insert("else", TokenType::Else_k);
+    map.insert("while", TokenType::While_k);
+    map.insert("break", TokenType::Break_k);
+    map.insert("continue", TokenType::Continue_k);
+    map.insert("for", TokenType::For_k);
+    map.insert("in", TokenType::In_k);
     map
 });
 
-pub unsafe fn get_flag(flag: &str, attr: Option<Attr>) -> Option<Flags> {
+pub const FLAG_NAMES: [&str; 5] = ["<asg>", "<const>", "<structure>", "<complex>", "<overflow>"];
+pub const ATTR_NAMES: [&str; 17] = [
+    "numeric", "string", "bool", "object", "array", "function", "complex",
+    "u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64", "f32", "f64",
+];
+
+pub fn get_flag(flag: &str, attr: Option<Attr>) -> Result<Flags, &'static str> {
    match flag {
         "<asg>" => {
-            Some(Flags::Assign_f)
+            Ok(Flags::Assign_f)
         },
         "<const>" => {
-            Some(Flags::Const_f)
+            Ok(Flags::Const_f)
         },
         "<structure>" => {
-            let unwrap = attr.unwrap_or_else(||{
-                panic!("Missing Attr")
-            });
-            Some(Flags::Struct_f(unwrap.clone()))
+            let unwrap = attr.ok_or("Missing Attr")?;
+            Ok(Flags::Struct_f(unwrap.clone()))
         },        
         "<complex>" => {
-            let unwrap = attr.unwrap_or_else(||{
-                panic!("Missing Attr")
-            });
-            Some(Flags::Complex_f(unwrap.clone()))
+            let unwrap = attr.ok_or("Missing Attr")?;
+            Ok(Flags::Complex_f(unwrap.clone()))
+        },
+        "<overflow>" => {
+            let policy = match attr.ok_or("Missing Attr")? {
+                Attr::Complex(policy) => [Overflow::Checked, Overflow::Wrapping, Overflow::Saturating].into_iter().find(|p| p.name() == policy),
+                _ => None,
+            };
+            Ok(Flags::Overflow_f(policy.ok_or("Unknown overflow policy; expected checked, wrapping or saturating")?))
         },
         _ => {
-            panic!("Unrecognised Flag")
+            Err("Unrecognised Flag")
         }
    } 
 }
 
-pub unsafe fn get_attr(atr: Option<&str>) -> Option<Attr> {
+pub fn get_attr(atr: Option<&str>) -> Option<Attr> {
     if let Some(attr) = atr {
         match attr {
             "numeric" => {
@@ -115,11 +199,17 @@ pub unsafe fn get_attr(atr: Option<&str>) -> Option<Attr> {
             "array" => {
                 Some(Attr::Array)
             }
+            "function" => {
+                Some(Attr::Function)
+            },
             "complex" => {
                 Some(Attr::ComplexKind)
             },
             _ => {
-                Some(Attr::Complex(attr.to_string()))
+                match NUM_KINDS.into_iter().find(|kind| kind.name() == attr) {
+                    Some(kind) => Some(Attr::Fixed(kind)),
+                    None => Some(Attr::Complex(attr.to_string())),
+                }
             }
         } 
     }else{
@@ -127,224 +217,208 @@ pub unsafe fn get_attr(atr: Option<&str>) -> Option<Attr> {
     }
 }
 
-pub unsafe fn tokenize(src_commented: String) -> Vec<Token>{
-    let src = src_commented;
-    let mut tokens: Vec<Token> = vec![];
-    let mut source: Vec<String> = src.chars().map(|x| x.to_string()).collect();
-    let mut line_no:isize = 1;
-    let mut char_no: isize = 0;
-
-    while source.len() > 0{
-        if source[0] == "\n" {
-            line_no += 1;
-            char_no = 0;
-            source.remove(0);
-            continue;
+// A `//` or `/* */` comment, kept verbatim for tools that rewrite source
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment{
+    pub text: String,
+    pub span: Span,
+}
+
+pub fn tokenize(src: String) -> Result<Vec<Token>, IonError>{
+    Ok(Lexer::new(&src).run()?.0)
+}
+
+// Same as `tokenize`, also handing back the comments the parser never sees
+pub fn tokenize_with_comments(src: String) -> Result<(Vec<Token>, Vec<Comment>), IonError>{
+    Lexer::new(&src).run()
+}
+
+// Single pass cursor over the source; `pos` is a byte offset, `line`/`col` count chars for diagnostics.
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+    line: isize,
+    col: isize,
+    tokens: Vec<Token>,
+    comments: Vec<Comment>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Lexer<'a> {
+        Lexer { src, pos: 0, line: 1, col: 0, tokens: vec![], comments: vec![] }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_next(&self) -> Option<char> {
+        self.rest().chars().nth(1)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.col = 0;
+        } else {
+            self.col += 1;
         }
-        match source[0].as_str() {
-            "/" => {
-                if source.get(1).expect("Abrupt code termination.") == "/" {
-                    while let Some(_) = source.get(0) {
-                        if source[0] != "\n" {
-                            source.remove(0);
-                            char_no += 1;
-                        } else {
-                            break;
-                        }
-                    }
-                } else if source.get(1).expect("Abrupt code termination") == "*" {
-                    source.remove(0);
-                    source.remove(0);
-                    char_no += 2;
-
-                    while let Some(_) = source.get(0) {
-                        if source[0] == "*" && source.get(1).expect("Abrupt code termination") == "/" {
-                            source.remove(0);
-                            source.remove(0);
-                            char_no += 2;
-                            break;
-                        } else {
-                            if source[0] == "\n" {
-                                line_no += 1;
-                                char_no = 0;
-                                source.remove(0);
-                            } else {
-                                char_no += 1;
-                                source.remove(0);
-                            }
-                        }
-                    }
-                } else {
-                    tokens.push(Token {
-                        value: source.remove(0),
-                        value_type: TokenType::BinOp,
-                        loc: (line_no, char_no)
-                    });
-                    char_no += 1;
-                }
-            }
-            "(" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::LeftParen, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            ";" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::Semicolon, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            ":" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::Colon, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            "." => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::Dot, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            "," => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::Comma, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            ")" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::RightParen, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            "[" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::LeftBrace, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            "]" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::RightBrace, loc: (line_no, char_no)});
-                char_no += 1;
-            },    
-            "{" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::LeftCurly, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            "}" => {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::RightCurly, loc: (line_no, char_no)});
-                char_no += 1;
-            },
-            "+" | "-" | "*" | "%"=> {
-                tokens.push(Token{value: source.remove(0), value_type: TokenType::BinOp, loc: (line_no, char_no)});
-                char_no += 1;
-            },
+        Some(c)
+    }
 
-            _ => {
-                if source[0].chars().collect::<Vec<char>>()[0].is_whitespace() {
-                    source.remove(0);
-                    char_no += 1;
-                    continue;
-                }
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&pred) {
+            self.bump();
+        }
+        &self.src[start..self.pos]
+    }
 
-                if source.len() > 0 && source[0].chars().next().unwrap().is_numeric() {
-                    let mut ta = String::new();
-                    let mut i = 0;
-                    let start_char = char_no;
-
-                    while i < source.len() {
-                        let ch = source[i].chars().next().unwrap();
-                        if ch.is_numeric() {
-                            ta += source[i].as_str();
-                            i += 1;
-                        } else {
-                            break;
-                        }
-                    }
-
-                    if i < source.len() && source[i] == "." && i + 1 < source.len() && source[i + 1].chars().next().unwrap().is_numeric() {
-                        ta += source[i].as_str(); 
-                        i += 1;
-
-                        while i < source.len() && source[i].chars().next().unwrap().is_numeric() {
-                            ta += source[i].as_str();
-                            i += 1;
-                        }
-                    }
-
-                    if !ta.is_empty() {
-                        tokens.push(Token {
-                            value: ta.clone(),
-                            value_type: TokenType::Number,
-                            loc: (line_no, start_char),
-                        });
-                        source.drain(..i);
-                        char_no += i as isize;
-                        continue;
-                    }
-                }
+    // Consumes `len` chars as one token of the given type
+    fn push(&mut self, len: usize, value_type: TokenType) {
+        let (start, loc) = (self.pos, (self.line, self.col));
+        for _ in 0..len {
+            self.bump();
+        }
+        self.tokens.push(Token{value: self.src[start..self.pos].to_string(), value_type, loc});
+    }
 
-               if source[0] == "<"{
-                   let mut ta = String::new();
-                   let start_char = char_no;
-                   let mut count = 0;
-
-                   while !ta.ends_with(">") && source.len() > 0 {
-                       ta += source.remove(0).as_str();
-                       count += 1;
-                   }
-
-                   tokens.push(Token{value: ta.clone(), value_type: TokenType::Flag(get_flag(parse_flag_head(ta.clone().as_str()).as_str(), get_attr(parse_attr( ta.clone().as_str() ))).unwrap()), loc: (line_no, start_char)});
-                   char_no += count;
-                   continue;
-               } 
-
-               if source.len() > 0 && !source[0].chars().collect::<Vec<char>>()[0].is_whitespace() {
-                   let mut i = 0;
-                   let mut ta = String::new();
-                   let start_char = char_no;
-                    while source.len() > i && !source[i].chars().collect::<Vec<char>>()[0].is_whitespace(){
-                        ta += source[i].as_str();
-                        i += 1;
-
-                    }
-                    if let Some(tax) = keywords.get(ta.as_str()){
-                        tokens.push(Token{value: ta.clone(), value_type: tax.clone(), loc: (line_no, start_char)});
-                        source.drain(..i);
-                        char_no += i as isize;
-                        continue;
-                    }
-                }
+    fn run(mut self) -> Result<(Vec<Token>, Vec<Comment>), IonError> {
+        while let Some(c) = self.peek() {
+            let next = self.peek_next();
+            match c {
+                '\n' => {
+                    self.bump();
+                },
+                '/' if next == Some('/') => {
+                    let loc = (self.line, self.col);
+                    let text = self.take_while(|c| c != '\n').trim_end();
+                    self.comment(text, loc);
+                },
+                '/' if next == Some('*') => self.block_comment()?,
+                '(' => self.push(1, TokenType::LeftParen),
+                ')' => self.push(1, TokenType::RightParen),
+                '[' => self.push(1, TokenType::LeftBrace),
+                ']' => self.push(1, TokenType::RightBrace),
+                '{' => self.push(1, TokenType::LeftCurly),
+                '}' => self.push(1, TokenType::RightCurly),
+                ';' => self.push(1, TokenType::Semicolon),
+                ':' => self.push(1, TokenType::Colon),
+                ',' => self.push(1, TokenType::Comma),
+                '@' => self.push(1, TokenType::RetType),
+                '.' if next == Some('.') => self.push(2, TokenType::DotDot),
+                '.' => self.push(1, TokenType::Dot),
+                '+' | '-' | '*' | '%' | '/' => self.push(1, TokenType::BinOp),
+                '=' | '!' if next == Some('=') => self.push(2, TokenType::BinOp),
+                '!' => self.push(1, TokenType::BinOp),
+                '=' => {
+                    return Err(IonError::lex(format!("Tok [ {:?} ] not found", c.to_string()), Span::at((self.line, self.col))));
+                },
+                '&' | '|' if next == Some(c) => self.push(2, TokenType::BinOp),
+                '<' | '>' if !is_flag_start(self.rest()) => {
+                    let len = if next == Some('=') { 2 } else { 1 };
+                    self.push(len, TokenType::BinOp);
+                },
+                c if c.is_whitespace() => {
+                    self.bump();
+                },
+                c if c.is_ascii_digit() => self.number(),
+                '<' => self.flag()?,
+                _ => self.word()?,
+            }
+        }
+        self.tokens.push(Token{value:String::new(), value_type: TokenType::EOF, loc: (self.line, self.col)});
 
-               if source[0] == "\"" {
-                   let mut ta = String::new();
-                   let start_char = char_no;
-                   source.remove(0);
-                   char_no += 1;
-                   while source.get(0).expect("Missing closing [ \" ]") != "\"" {
-                        ta += source.remove(0).as_str();
-                        char_no += 1;
-                   }
-                   source.remove(0);
-                   char_no += 1;
-                   tokens.push(Token{value: ta.clone(), value_type: TokenType::String, loc: (line_no, start_char)});
-                   continue;
-                }
+        Ok((self.tokens, self.comments))
+    }
 
-               if source.len() > 0 && is_identifier(source[0].as_str()) {
-                   let mut ta = String::new();
-                   let start_char = char_no;
-                   let mut count = 0;
-                   while source.len() > 0 && is_identifier(source[0].as_str()){
-                       ta += source.remove(0).as_str();
-                       count += 1;
-                   }
-                   tokens.push(Token{value: ta.clone(), value_type: TokenType::Identifier, loc: (line_no, start_char)});
-                   char_no += count;
-                   continue;
-               }
-               
-               panic!("{}", format!("Tok [ {:?} ] not found | {}:{}", source[0], line_no, char_no));
+    fn comment(&mut self, text: &str, loc: (isize, isize)) {
+        let end = (self.line, self.col - 1);
+        self.comments.push(Comment{text: text.to_string(), span: Span::new(loc, end)});
+    }
+
+    fn block_comment(&mut self) -> Result<(), IonError> {
+        let (start, loc) = (self.pos, (self.line, self.col));
+        self.bump();
+        self.bump();
+        while !self.rest().starts_with("*/") {
+            if self.bump().is_none() {
+                return Err(IonError::lex("Abrupt code termination; missing closing [ */ ]".to_string(), Span::at((self.line, self.col))));
             }
         }
+        self.bump();
+        self.bump();
+        self.comment(&self.src[start..self.pos], loc);
+        Ok(())
+    }
+
+    fn number(&mut self) {
+        let (start, loc) = (self.pos, (self.line, self.col));
+        self.take_while(|c| c.is_ascii_digit());
+        if self.peek() == Some('.') && self.peek_next().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+            self.take_while(|c| c.is_ascii_digit());
+        }
+        self.tokens.push(Token{value: self.src[start..self.pos].to_string(), value_type: TokenType::Number, loc});
+    }
+
+    fn flag(&mut self) -> Result<(), IonError> { // <asg> | <structure: numeric> ...
+        let (start, loc) = (self.pos, (self.line, self.col));
+        self.take_while(|c| c != '>');
+        self.bump();
+        let ta = &self.src[start..self.pos];
+        let count = ta.chars().count() as isize;
+
+        let head = parse_flag_head(ta);
+        let flag = get_flag(head.as_str(), get_attr(parse_attr(ta))).map_err(|msg| {
+            let err = IonError::lex(format!("{} [ {} ]", msg, ta), Span::new(loc, (loc.0, loc.1 + count - 1)));
+            match suggest(&head, FLAG_NAMES) {
+                Some(name) => err.with_help(format!("did you mean `{}`?", name)),
+                None => err,
+            }
+        })?;
+        self.tokens.push(Token{value: ta.to_string(), value_type: TokenType::Flag(flag), loc});
+        Ok(())
     }
-    tokens.push(Token{value:String::new(), value_type: TokenType::EOF, loc: (line_no, char_no)});
 
-    if PRINT_{
-        println!("\n-------------------------- Lexer -------------------------------\n");
-        println!("{:?}\n", tokens);
-        println!("-------------------------- Abstract Syntax Tree -------------------------------\n");
+    fn word(&mut self) -> Result<(), IonError> {
+        let loc = (self.line, self.col);
+
+        // Whitespace-delimited keywords first, so symbols like `|` are recognised too
+        let word = self.rest().split(char::is_whitespace).next().unwrap_or("");
+        if let Some(tax) = keywords.get(word) {
+            self.push(word.chars().count(), tax.clone());
+            return Ok(());
+        }
+
+        let c = self.peek().unwrap_or_default();
+        if c == '"' {
+            self.bump();
+            let content = self.take_while(|c| c != '"');
+            if self.bump().is_none() {
+                return Err(IonError::lex("Missing closing [ \" ]".to_string(), Span::at(loc)));
+            }
+            self.tokens.push(Token{value: content.to_string(), value_type: TokenType::String, loc});
+            return Ok(());
+        }
+
+        if is_identifier(c) {
+            // Digits may follow the first character, so `u8` names a type
+            let ta = self.take_while(|c| is_identifier(c) || c.is_ascii_digit());
+            let value_type = keywords.get(ta).cloned().unwrap_or(TokenType::Identifier);
+            self.tokens.push(Token{value: ta.to_string(), value_type, loc});
+            return Ok(());
+        }
+
+        Err(IonError::lex(format!("Tok [ {:?} ] not found", c.to_string()), Span::at(loc)))
     }
-    return tokens;
-}   
+}
 
 fn parse_flag_head(s: &str) -> String {
     match s.find(':') {
@@ -365,6 +439,19 @@ fn parse_attr(s: &str) -> Option<&str> {
     }
 }
 
-fn is_identifier(c: &str) -> bool {
-    return c.chars().collect::<Vec<char>>()[0].is_alphabetic() || c.chars().collect::<Vec<char>>()[0] == '_';
+fn is_flag_start(source: &str) -> bool {
+    let Some(after_angle) = source.strip_prefix('<') else {
+        return false;
+    };
+    let head: String = after_angle.chars().take_while(|c| is_identifier(*c)).collect();
+    if matches!(head.as_str(), "asg" | "const" | "structure" | "complex" | "overflow") {
+        return true;
+    }
+    // A misspelt flag (<structre: numeric>) still lexes as one so it can be reported as such
+    let after = after_angle[head.len()..].chars().next();
+    matches!(after, Some(':') | Some('>')) && suggest(&format!("<{}>", head), FLAG_NAMES).is_some()
+}
+
+fn is_identifier(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
 }
@@ -0,0 +1,147 @@
+// Lets `#[ion_native]` expand to `::ion::` paths inside this crate as well as in hosts
+extern crate self as ion;
+
+pub mod lexer;
+pub mod parser;
+pub mod ast;
+pub mod error;
+pub mod diagnostic;
+pub mod formatter;
+pub mod checker;
+
+pub mod runtime;
+pub use runtime::*;
+pub use ion_macros::ion_native;
+
+use std::cell::{Ref, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+use ast::{Identifier, Program};
+use error::{IonError, Span};
+use interpreter::{infer_flags, static_type_check, unwrap_runtime_value_serve, RuntimeValueServe};
+use lexer::{Attr, Flags};
+use scopes::{init, Scope};
+use values::{Callable, NativeFnValue, RuntimeValue};
+
+pub type Value = Box<dyn RuntimeValue>;
+
+// An embeddable Ion session: every evaluation shares one global scope, so declarations
+// made by one script (or by the host through `set_global`) are visible to the next.
+pub struct Interpreter {
+    scope: Rc<RefCell<Scope>>,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl Interpreter {
+    pub fn new() -> Interpreter {
+        Interpreter::with_args(vec![])
+    }
+
+    // Same as `new`, with `args` exposed to scripts through the global `args` array
+    pub fn with_args(args: Vec<String>) -> Interpreter {
+        Interpreter { scope: Rc::new(RefCell::new(init(args))) }
+    }
+
+    pub fn eval_str(&mut self, source: &str) -> Result<Value, IonError> {
+        self.eval(parser::prod_ast(source.to_string())?)
+    }
+
+    // Type checks `program` against the current globals, before it runs (see `checker::check`)
+    pub fn check(&self, program: &Program) -> Vec<IonError> {
+        checker::check(program, &self.scope.borrow())
+    }
+
+    // Runs `program` if it type checks, failing with the first type error otherwise
+    pub fn eval(&mut self, program: Program) -> Result<Value, IonError> {
+        if let Some(err) = self.check(&program).into_iter().next() {
+            return Err(err);
+        }
+        let result = interpreter::eval_program(&program, &self.scope)?;
+        unwrap_runtime_value_serve(result, &self.scope)
+    }
+
+    // Declares `name` with flags inferred from the value, or reassigns it (type checked
+    // against its `<structure>`) when the global already exists.
+    pub fn set_global(&mut self, name: &str, value: Value) -> Result<(), IonError> {
+        let exists = self.scope.borrow().variables.contains_key(name);
+        if !exists {
+            let flags = infer_flags(value.as_ref());
+            self.scope.borrow_mut().var_decl(name.to_string(), RuntimeValueServe::Owned(value), flags)?;
+            return Ok(());
+        }
+
+        let flags = self.scope.borrow().lookup_flags(name.to_string())?;
+        let structure = flags.iter().find_map(|flag| match flag {
+            Flags::Struct_f(attr) => Some(attr.clone()),
+            _ => None,
+        });
+        let complex = flags.iter().find_map(|flag| match flag {
+            Flags::Complex_f(Attr::Complex(symbol)) => Some(Identifier{symbol: symbol.clone(), span: Span::default()}),
+            _ => None,
+        });
+        if let Some(attr) = structure {
+            static_type_check(value.clone(), attr, complex, &self.scope)?;
+        }
+        self.scope.borrow_mut().var_assign(name.to_string(), RuntimeValueServe::Owned(value))?;
+        Ok(())
+    }
+
+    pub fn get_global(&self, name: &str) -> Result<Value, IonError> {
+        let value = self.scope.borrow().lookup(name.to_string())?;
+        unwrap_runtime_value_serve(value, &self.scope)
+    }
+
+    // Exposes a host function to scripts as a const global `name`
+    pub fn register_native<F>(&mut self, name: &str, call: F) -> Result<(), IonError>
+    where
+        F: Fn(Vec<RuntimeValueServe>, &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> + Clone + 'static,
+    {
+        self.register(name, NativeFnValue{call: Box::new(HostFn{name: name.to_string(), call})})
+    }
+
+    // Same as `register_native`, for natives built by `#[ion_native]` (`register("pow", pow_native())`)
+    pub fn register(&mut self, name: &str, native: NativeFnValue) -> Result<(), IonError> {
+        self.scope.borrow_mut().var_decl(
+            name.to_string(),
+            RuntimeValueServe::Owned(Box::new(native)),
+            vec![Flags::Const_f, Flags::Struct_f(Attr::Function)],
+        )?;
+        Ok(())
+    }
+
+    pub fn globals(&self) -> Ref<'_, Scope> {
+        self.scope.borrow()
+    }
+}
+
+// Closures aren't `Debug`, which the blanket `Callable` impl needs, so host functions carry their name instead.
+#[derive(Clone)]
+struct HostFn<F> {
+    name: String,
+    call: F,
+}
+
+impl<F> fmt::Debug for HostFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HostFn({})", self.name)
+    }
+}
+
+impl<F> Callable for HostFn<F>
+where
+    F: Fn(Vec<RuntimeValueServe>, &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> + Clone + 'static,
+{
+    fn call_fn(&self, args: Vec<RuntimeValueServe>, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+        (self.call)(args, scope)
+    }
+
+    fn clone_box(&self) -> Box<dyn Callable> {
+        Box::new(self.clone())
+    }
+}
@@ -1,33 +1,146 @@
-use std::cell::RefCell;
-use std::fs;
-mod lexer;
-mod parser;
-mod ast;
+use std::io::{self, Read};
+use std::{env, fs, process};
+mod fmt_runner;
+mod repl;
+mod test_runner;
 
-mod runtime;
-use runtime::*;
+use ion::error::IonError;
+use ion::scopes::Scope;
+use ion::{diagnostic, lexer, parser, Interpreter};
 
-use crate::scopes::init;
+const USAGE: &str = "\
+Usage: ion [OPTIONS] [--] <script | -> [ARGS]...
+       ion repl [ARGS]...
+       ion test [--bless] [PATHS]...
+       ion fmt [--check] [PATHS | -]...
 
-fn main() {
-    let source = fs::read_to_string("main.io")
-        .expect("Failed to read file 'main.io'");
-    unsafe {
-        if PRINT_ {
-            println!("\n-------------------------- Original -------------------------------\n");
-            println!("{}\n", source);
+Runs an Ion script; `-` reads the script from stdin and `repl` starts an interactive session.
+`test` runs every .io script under PATHS (default: tests/) against its .out file and
+`// expect-error: <code>` annotation; `--bless` rewrites the .out files instead.
+`fmt` rewrites every .io script under PATHS (default: .) in the canonical layout; `--check`
+only reports the scripts that aren't formatted, and `-` formats stdin to stdout.
+Any ARGS after the script are exposed to it through the global `args` array. A script named
+like a subcommand (`repl`, `test`, `fmt`) is run by putting `--` before it: `ion -- test`.
+
+Options:
+  --dump-tokens   Print the token stream produced by the lexer
+  --dump-ast      Print the abstract syntax tree produced by the parser
+  --dump-scope    Print the global scope once the script has finished
+  -h, --help      Print this message";
+
+#[derive(Default)]
+struct Options {
+    dump_tokens: bool,
+    dump_ast: bool,
+    dump_scope: bool,
+    // `repl`, `test` or `fmt`, when one is given instead of a script
+    command: Option<String>,
+    script: String,
+    args: Vec<String>,
+}
+
+fn parse_options(mut argv: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    loop {
+        let Some(arg) = argv.next() else {
+            return Err("missing script path".to_string());
+        };
+        match arg.as_str() {
+            "--dump-tokens" => options.dump_tokens = true,
+            "--dump-ast" => options.dump_ast = true,
+            "--dump-scope" => options.dump_scope = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            },
+            "--" => {
+                options.script = argv.next().ok_or("missing script path")?;
+                options.args = argv.collect();
+                return Ok(options);
+            },
+            flag if flag.starts_with("--") => return Err(format!("unknown option [ {} ]", flag)),
+            "repl" | "test" | "fmt" => {
+                options.command = Some(arg);
+                options.args = argv.collect();
+                return Ok(options);
+            },
+            _ => {
+                options.script = arg;
+                options.args = argv.collect();
+                return Ok(options);
+            }
         }
+    }
+}
 
-        let output = parser::prod_ast(source.clone());
-        if PRINT_ {
-            println!("{:?}\n", output);
+fn main() {
+    let options = parse_options(env::args().skip(1)).unwrap_or_else(|msg| {
+        eprintln!("error: {}\n\n{}", msg, USAGE);
+        process::exit(2);
+    });
+
+    match options.command.as_deref() {
+        Some("repl") => {
+            repl::run(options.args);
+            return;
+        },
+        Some("test") => process::exit(test_runner::run(options.args)),
+        Some("fmt") => process::exit(fmt_runner::run(options.args)),
+        _ => {},
+    }
+
+    let (file, source) = if options.script == "-" {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source).unwrap_or_else(|e| {
+            eprintln!("error: failed to read script from stdin: {}", e);
+            process::exit(1);
+        });
+        ("<stdin>".to_string(), source)
+    } else {
+        let source = fs::read_to_string(&options.script).unwrap_or_else(|e| {
+            eprintln!("error: failed to read [ {} ]: {}", options.script, e);
+            process::exit(1);
+        });
+        (options.script.clone(), source)
+    };
+    let fail = |err: IonError| -> ! {
+        eprintln!("{}", diagnostic::render(&err, &file, &source));
+        process::exit(1);
+    };
+
+    if options.dump_tokens {
+        let tokens = lexer::tokenize(source.clone()).unwrap_or_else(|err| fail(err));
+        println!("-------------------------- Lexer -------------------------------\n");
+        println!("{:?}\n", tokens);
+    }
+
+    let output = parser::prod_ast(source.clone()).unwrap_or_else(|err| fail(err));
+    if options.dump_ast {
+        println!("-------------------------- Abstract Syntax Tree -------------------------------\n");
+        println!("{:?}\n", output);
+    }
+
+    let mut interpreter = Interpreter::with_args(options.args);
+    // Every type error is reported at once, before the script gets to run
+    let errors = interpreter.check(&output);
+    if !errors.is_empty() {
+        for err in &errors {
+            eprintln!("{}", diagnostic::render(err, &file, &source));
         }
-        println!("----------------------------Logs----------------------------------");
+        process::exit(1);
+    }
+    interpreter.eval(output).unwrap_or_else(|err| fail(err));
+    if options.dump_scope {
+        println!("\n-------------------------- Scope -------------------------------\n");
+        dump_scope(&interpreter.globals());
+    }
+}
 
-        let evaluated = interpreter::evaluate(Box::new(output.clone()), Box::leak(Box::new(RefCell::new(init()))));
-        if PRINT_ {
-            println!("\n-------------------------- Runtime -------------------------------\n");
-            println!("Runtime Value Debug: {:?}\n", evaluated);
-        } 
+fn dump_scope(scope: &Scope) {
+    let mut names: Vec<&String> = scope.variables.keys().collect();
+    names.sort();
+    for name in names {
+        let entry = &scope.variables[name];
+        println!("{} {:?} = {}", name, entry.flags, entry.value);
     }
 }
@@ -1,363 +1,559 @@
-use std::{fmt::Debug, str::FromStr};
+use std::str::FromStr;
 use crate::{ast::*, lexer::*};
+use crate::error::{IonError, Span};
 use num_traits::Num;
 
-static mut TOKENS: Vec<Token> = vec![];
+pub fn prod_ast(source_c: String) -> Result<Program, IonError> {
+    Parser::new(tokenize(source_c)?).parse_program()
+}
+
+// Recursive descent parser over a token cursor; each instance owns its own tokens.
+pub struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    prev_end: (isize, isize), // end of the last consumed token
+}
 
-pub unsafe fn prod_ast(source_c: String) -> Program {
-    TOKENS = tokenize(source_c);
+impl Parser {
+    pub fn new(mut tokens: Vec<Token>) -> Parser {
+        if tokens.last().map(|tok| &tok.value_type) != Some(&TokenType::EOF) {
+            let loc = tokens.last().map(|tok| tok.loc).unwrap_or((1, 0));
+            tokens.push(Token{value: String::new(), value_type: TokenType::EOF, loc});
+        }
+        Parser { tokens, pos: 0, prev_end: (0, 0) }
+    }
 
-    let mut program = Program{body: vec![]};
+    pub fn parse_program(&mut self) -> Result<Program, IonError> {
+        let mut program = Program{body: vec![], span: Span::at(self.peek().loc)};
 
-    while TOKENS.len() > 0{
-        if TOKENS.first().is_some() {
-            if TOKENS.first().unwrap().value_type == TokenType::EOF {
-                break;
-            }
+        while self.peek().value_type != TokenType::EOF {
+            program.body.push(self.parse_to_stmt()?);
         }
+        program.span = Span::new(program.span.start, self.prev_end);
 
-        program.body.push(parse_to_stmt());
+        Ok(program)
     }
 
-    program
-}
+    pub fn peek(&self) -> &Token {
+        self.peek_at(0)
+    }
+
+    pub fn peek_at(&self, offset: usize) -> &Token {
+        self.tokens.get(self.pos + offset).unwrap_or(&self.tokens[self.tokens.len() - 1])
+    }
 
-unsafe fn parse_to_stmt() -> Box<dyn Stmt>{
-    match TOKENS[0].value_type {
-        TokenType::Let_k => {
-            let rt = parse_var_decl();
-            end_stmt();
-            return rt;
-        },
-        TokenType::Identifier => {
-            if TOKENS[1].value_type == TokenType::Flag(Flags::Assign_f) || (TOKENS[1].value_type == TokenType::Dot && TOKENS[3].value_type == TokenType::Flag(Flags::Assign_f)){
-                let rt = parse_var_asg();
-                end_stmt();
-                return rt;
-            }else{
-                let rt = parse_expr();
-                end_stmt();
-                return rt;
+    fn parse_to_stmt(&mut self) -> Result<Stmt, IonError> {
+        match self.peek().value_type {
+            TokenType::Let_k => {
+                let rt = self.parse_var_decl()?;
+                self.end_stmt()?;
+                return Ok(rt);
+            },
+            TokenType::Return_k => {
+                let rt = self.parse_return()?;
+                self.end_stmt()?;
+                return Ok(rt);
+            },
+            TokenType::If_k => {
+                return self.parse_if();
+            },
+            TokenType::While_k => {
+                return self.parse_while();
+            },
+            TokenType::For_k => {
+                return self.parse_for();
+            },
+            TokenType::Break_k => {
+                let span = token_span(&self.advance());
+                self.end_stmt()?;
+                return Ok(Break{span}.into());
+            },
+            TokenType::Continue_k => {
+                let span = token_span(&self.advance());
+                self.end_stmt()?;
+                return Ok(Continue{span}.into());
+            },
+            TokenType::Identifier if self.peek_at(1).value_type == TokenType::Flag(Flags::Assign_f) || (self.peek_at(1).value_type == TokenType::Dot && self.tokens.get(self.pos + 3).is_some_and(|t| t.value_type == TokenType::Flag(Flags::Assign_f))) => {
+                let rt = self.parse_var_asg()?;
+                self.end_stmt()?;
+                return Ok(rt);
+            },
+            _ => {
+                let rt =  self.parse_expr()?;
+                self.end_stmt()?;
+                return Ok(Stmt::Expr(rt));
             }
+        }
+    }
 
-        },
-        _ => {
-            let rt =  parse_expr();
-            end_stmt();
-            return rt;
+    fn parse_var_asg(&mut self) -> Result<Stmt, IonError> {
+        let lhs = self.parse_expr()?;
+        self.expect(TokenType::Flag(Flags::Assign_f))?;
+        if self.peek().value_type != TokenType::EOF {
+            let rhs = self.parse_expr()?;
+            let span = Span::new(lhs.span().start, rhs.span().end);
+            return Ok(VarAsg{lhs: Box::new(lhs), rhs: Box::new(rhs), span}.into())
+        }else{
+            Err(ERROR("Incomplete variable assignment", self.peek().clone()))
         }
     }
-}
 
-unsafe fn parse_var_asg() -> Box<dyn Stmt> {
-    let lhs = parse_expr();
-    expect(TokenType::Flag(Flags::Assign_f));
-    if TOKENS.len() > 1{
-        let rhs = parse_expr();
-        return Box::new(VarAsg{lhs: lhs, rhs: rhs})
-    }else{
-        ERROR("Incomplete variable assignment", TOKENS[0].clone());
+    fn parse_return(&mut self) -> Result<Stmt, IonError> {
+        let start = self.expect(TokenType::Return_k)?.loc;
+        let mut value: Expr = Nil {span: Span::at(self.prev_end)}.into();
+        if self.peek().value_type != TokenType::Semicolon {
+            value = self.parse_expr()?;
+        }
+        Ok(Return{value: Box::new(value), span: self.span_from(start)}.into())
     }
-}
 
-unsafe fn parse_var_decl() -> Box<dyn Stmt> {
-    expect(TokenType::Let_k);
-    let ident = expect(TokenType::Identifier);
+    fn parse_if(&mut self) -> Result<Stmt, IonError> { // if cond { ... } else if cond { ... } else { ... }
+        let start = self.expect(TokenType::If_k)?.loc;
+        let condition = self.parse_expr()?;
+        let body = self.parse_block()?;
+        let mut alternate = None;
+        if self.peek().value_type == TokenType::Else_k {
+            self.advance();
+            if self.peek().value_type == TokenType::If_k {
+                alternate = Some(vec![self.parse_if()?]);
+            } else {
+                alternate = Some(self.parse_block()?);
+            }
+        }
+        Ok(IfStmt{condition: Box::new(condition), body, alternate, span: self.span_from(start)}.into())
+    }
 
-    let mut found_flags = vec![];
+    fn parse_while(&mut self) -> Result<Stmt, IonError> { // while cond { ... }
+        let start = self.expect(TokenType::While_k)?.loc;
+        let condition = self.parse_expr()?;
+        let body = self.parse_block()?;
+        Ok(WhileStmt{condition: Box::new(condition), body, span: self.span_from(start)}.into())
+    }
 
-    while let TokenType::Flag(ref flag) = TOKENS[0].value_type {
-        let flag = flag.clone(); // clone the flag so you keep ownership
-        TOKENS.remove(0);
-        found_flags.push(flag);
+    fn parse_for(&mut self) -> Result<Stmt, IonError> { // for x in arr { ... } | for i in 0..n { ... }
+        let start = self.expect(TokenType::For_k)?.loc;
+        let item = self.expect(TokenType::Identifier)?.value;
+        self.expect(TokenType::In_k)?;
+        let iterable = self.parse_expr()?;
+        let body = self.parse_block()?;
+        Ok(ForStmt{item, iterable: Box::new(iterable), body, span: self.span_from(start)}.into())
     }
 
-    let mut value: Box<dyn Expr> = Box::new(Nil {});
+    fn parse_var_decl(&mut self) -> Result<Stmt, IonError> {
+        let start = self.expect(TokenType::Let_k)?.loc;
+        let ident = self.expect(TokenType::Identifier)?;
+
+        let mut found_flags = vec![];
+
+        while let TokenType::Flag(ref flag) = self.peek().value_type {
+            let flag = flag.clone(); // clone the flag so you keep ownership
+            self.advance();
+            found_flags.push(flag);
+        }
+
+        let mut value: Expr = Nil {span: Span::at(self.prev_end)}.into();
 
-    if found_flags.contains(&Flags::Assign_f) {
-        value = parse_expr(); 
+        if found_flags.contains(&Flags::Assign_f) {
+            value = self.parse_expr()?; 
+        }
+
+        Ok(VarDeclaration {
+            identifier: ident.value,
+            flags: found_flags,
+            value: Box::new(value),
+            span: self.span_from(start),
+        }.into())
     }
 
-    Box::new(VarDeclaration {
-        identifier: ident.value,
-        flags: found_flags,
-        value,
-    })
-}
+    fn parse_expr(&mut self) -> Result<Expr, IonError> {
+        return self.parse_object_literal_expr();
+    }
 
-unsafe fn parse_expr() -> Box<dyn Expr>{
-    return parse_object_literal_expr();
-}
+    fn parse_object_literal_expr(&mut self) -> Result<Expr, IonError> {
+       if self.peek().value_type != TokenType::LeftCurly{
+            return self.parse_object_expr();
+       }
+       let start = self.expect(TokenType::LeftCurly)?.loc;
+       let mut props = vec![];
+       while self.peek().value_type == TokenType::Identifier {
+            let key_tok = self.advance();
+            self.expect(TokenType::Colon)?;
+            let value = self.parse_expr()?;
+            self.expect(TokenType::Semicolon)?;
+            props.push(PropertyLiteral{key: key_tok.value, value, span: self.span_from(key_tok.loc)});
+       }
+       self.expect(TokenType::RightCurly)?;
+       return Ok(ObjectLiteral{properties: props, span: self.span_from(start)}.into())
+    }
 
-unsafe fn parse_object_literal_expr() -> Box<dyn Expr> {
-   if TOKENS[0].value_type != TokenType::LeftCurly{
-        return parse_object_expr();
-   }
-   expect(TokenType::LeftCurly);
-   let mut props = vec![];
-   while TOKENS[0].value_type == TokenType::Identifier {
-        let key = TOKENS.remove(0).value;
-        expect(TokenType::Colon);
-        let value = parse_expr();
-        expect(TokenType::Semicolon);
-        props.push(PropertyLiteral{key, value});
-   }
-   expect(TokenType::RightCurly);
-   return Box::new(ObjectLiteral{properties: props})
-}
+    fn parse_object_expr(&mut self) -> Result<Expr, IonError> {
+       if self.peek().value_type != TokenType::obj_struct_k{
+            return self.parse_array_literal_expr();
+       }
+       let start = self.advance().loc;
+       self.expect(TokenType::LeftCurly)?;
+       let mut props = vec![];
+       while self.peek().value_type == TokenType::Identifier {
+            let key_tok = self.advance();
+            self.expect(TokenType::Colon)?;
+            let tok = self.peek().clone();
+            let value = get_attr(Some(self.advance().value.as_str())).ok_or_else(||{
+                ERROR("Incorrect type attr provided for object key", tok)
+            })?;
+            self.expect(TokenType::Semicolon)?;
+            props.push(Property{key: key_tok.value, value, span: self.span_from(key_tok.loc)});
+       }
+       self.expect(TokenType::RightCurly)?;
+       return Ok(Object{properties: props, span: self.span_from(start)}.into())
+    }
 
-unsafe fn parse_object_expr() -> Box<dyn Expr> {
-   if TOKENS[0].value_type != TokenType::obj_struct_k{
-        return parse_array_literal_expr();
-   }
-   TOKENS.remove(0);
-   expect(TokenType::LeftCurly);
-   let mut props = vec![];
-   while TOKENS[0].value_type == TokenType::Identifier {
-        let key = TOKENS.remove(0).value;
-        expect(TokenType::Colon);
-        let tok = TOKENS[0].clone();
-        let value = get_attr(Some(TOKENS.remove(0).value.as_str())).unwrap_or_else(||{
-            ERROR("Incorrect type attr provided for object key", tok);
-        });
-        expect(TokenType::Semicolon);
-        props.push(Property{key, value});
-   }
-   expect(TokenType::RightCurly);
-   return Box::new(Object{properties: props})
-}
+    fn parse_array_literal_expr(&mut self) -> Result<Expr, IonError> {
+       if self.peek().value_type != TokenType::LeftBrace{
+            return self.parse_array_expr();
+       }
+       let start = self.advance().loc;
+       let mut entries = vec![];
+       while self.peek().value_type != TokenType::RightBrace {
+           entries.push(self.parse_expr()?);
+           if self.peek().value_type == TokenType::RightBrace {break;};
+           self.advance();
+       }
+       self.advance();
+       return Ok(ArrayLiteral{entries, span: self.span_from(start)}.into())
+    }
 
-unsafe fn parse_array_literal_expr() -> Box<dyn Expr> {
-   if TOKENS[0].value_type != TokenType::LeftBrace{
-        return parse_array_expr();
-   }
-   TOKENS.remove(0);
-   let mut entries = vec![];
-   while TOKENS[0].value_type != TokenType::RightBrace {
-       entries.push(parse_expr());
-       if TOKENS[0].value_type == TokenType::RightBrace {break;};
-       TOKENS.remove(0);
-   }
-   TOKENS.remove(0);
-   return Box::new(ArrayLiteral{entries})
-}
 
+    fn parse_array_expr(&mut self) -> Result<Expr, IonError> { // [numeric ; nil ; 10;]
+       if self.peek().value_type != TokenType::arr_struct_k{
+            return self.parse_fn_struct();
+       }
+       let start = self.advance().loc;
+       self.expect(TokenType::LeftBrace)?;
+       let attr_shell = self.advance();
+       let attr = get_attr(Some(attr_shell.value.as_str())).ok_or_else(||{
+            ERROR("Incorrect type attr provided for array struct", attr_shell.clone())
+       })?;
+       self.expect(TokenType::Semicolon)?;
+       let complex_attr_shell = self.advance();
+       let mut complex_attr = None;
+       if complex_attr_shell.value_type != TokenType::Nil_k{
+            complex_attr = Some(complex_attr_shell.value);
+       }
+       self.expect(TokenType::Semicolon)?;
+       let length_shell = self.advance();
+       let length = usize::from_str( &(length_shell.value)).map_err(|_|{
+            ERROR("Array struct length must be a non-negative integer", length_shell.clone())
+       })?;
+       self.expect(TokenType::Semicolon)?;
+       self.expect(TokenType::RightBrace)?;
+       return Ok(Array{attr, complex_attr, length, span: self.span_from(start)}.into());
+    }
 
-unsafe fn parse_array_expr() -> Box<dyn Expr> { // [numeric ; nil ; 10;]
-   if TOKENS[0].value_type != TokenType::arr_struct_k{
-        return parse_fn_struct();
-   }
-   TOKENS.remove(0);
-   expect(TokenType::LeftBrace);
-   let attr_shell = TOKENS.remove(0);
-   let attr = get_attr(Some(attr_shell.value.as_str()));
-   expect(TokenType::Semicolon);
-   let complex_attr_shell = TOKENS.remove(0);
-   let mut complex_attr = None;
-   if complex_attr_shell.value_type != TokenType::Nil_k{
-        complex_attr = Some(complex_attr_shell.value);
-   }
-   expect(TokenType::Semicolon);
-   let length = usize::from_str( &(TOKENS.remove(0).value));
-   expect(TokenType::Semicolon);
-   expect(TokenType::RightBrace);
-   return Box::new(Array{attr: attr.unwrap(), complex_attr, length: length.unwrap()});
-}
+    fn parse_fn_struct(&mut self) -> Result<Expr, IonError> { // fn (x: numeric, y: numeric) @ numeric { ... }
+        if self.peek().value_type != TokenType::fn_struct_k { 
+            return self.parse_range_expr();
+        }
+        let start = self.advance().loc;
+        self.expect(TokenType::LeftParen)?;
+        let mut params = vec![];
+        while self.peek().value_type == TokenType::Identifier {
+            let param_tok = self.advance();
+            self.expect(TokenType::Colon)?;
+            let param_type = self.parse_type_attr("Incorrect type attr provided for function parameter")?;
+            params.push(Param{param: param_tok.value, param_type, span: self.span_from(param_tok.loc)});
+            if self.peek().value_type != TokenType::Comma {break;}
+            self.advance();
+        }
+        self.expect(TokenType::RightParen)?;
+        self.expect(TokenType::RetType)?;
+        let ret_type = self.parse_type_attr("Incorrect type attr provided for function return type")?;
+        let body = self.parse_block()?;
+        return Ok(FnStruct{params, ret_type, body, span: self.span_from(start)}.into());
+    }
 
-unsafe fn parse_fn_struct() -> Box<dyn Expr> {
-    if TOKENS[0].value_type != TokenType::fn_struct_k { 
-        return parse_additive_expr();
+    fn parse_type_attr(&mut self, msg: &str) -> Result<Attr, IonError> {
+        let tok = self.peek().clone();
+        get_attr(Some(self.advance().value.as_str())).ok_or_else(||{
+            ERROR(msg, tok)
+        })
     }
-    todo!();
-}
 
-unsafe fn parse_additive_expr() -> Box<dyn Expr> {
-    if TOKENS[0].value_type == TokenType::String && TOKENS[1].value == "+" {
-        let mut lhs = parse_multiplicative_expr();
-        while TOKENS[0].value == "+"{
-            let op = TOKENS.remove(0).value;
-            let rhs = parse_multiplicative_expr();
-            lhs = Box::new(BinExpr{
-                left: lhs,
-                operator: op,
-                right: rhs,
-            })
-            
+    fn parse_block(&mut self) -> Result<Vec<Stmt>, IonError> {
+        self.expect(TokenType::LeftCurly)?;
+        let mut body = vec![];
+        while self.peek().value_type != TokenType::RightCurly {
+            if self.peek().value_type == TokenType::EOF {
+                return Err(ERROR("Missing closing [ } ] for block", self.peek().clone()));
+            }
+            body.push(self.parse_to_stmt()?);
         }
-        return lhs;
+        self.expect(TokenType::RightCurly)?;
+        Ok(body)
     }
-    let mut left = parse_multiplicative_expr();
 
-    while !TOKENS.is_empty() && (TOKENS[0].clone().value == "+" || TOKENS[0].clone().value == "-") {
-        let op = TOKENS.remove(0).value;
-        let right = parse_multiplicative_expr();
-        left = Box::new(BinExpr {
-            left,
-            right,
-            operator: op,
-        });
+    fn parse_range_expr(&mut self) -> Result<Expr, IonError> {
+        let start = self.parse_logical_or_expr()?;
+        if self.peek().value_type != TokenType::DotDot {
+            return Ok(start);
+        }
+        self.advance();
+        let end = self.parse_logical_or_expr()?;
+        let span = Span::new(start.span().start, end.span().end);
+        Ok(RangeExpr{start: Box::new(start), end: Box::new(end), span}.into())
     }
 
-    left
-}
+    fn parse_logical_or_expr(&mut self) -> Result<Expr, IonError> {
+        let mut left = self.parse_logical_and_expr()?;
 
-unsafe fn parse_multiplicative_expr() -> Box<dyn Expr> {
-    let mut left = parse_call_mem_expr(); 
+        while self.peek().value == "||" && self.peek().value_type == TokenType::BinOp {
+            let op = self.advance().value;
+            let right = self.parse_logical_and_expr()?;
+            left = bin_expr(left, op, right);
+        }
 
-    while !TOKENS.is_empty() && (TOKENS[0].clone().value == "*" || TOKENS[0].clone().value == "/" || TOKENS[0].clone().value == "%") {
-        let op = TOKENS.remove(0).value;
-        let right = parse_prim_expr();
-        left = Box::new(BinExpr {
-            left,
-            right,
-            operator: op,
-        });
+        Ok(left)
     }
 
-    left
-}
+    fn parse_logical_and_expr(&mut self) -> Result<Expr, IonError> {
+        let mut left = self.parse_comparison_expr()?;
+
+        while self.peek().value == "&&" && self.peek().value_type == TokenType::BinOp {
+            let op = self.advance().value;
+            let right = self.parse_comparison_expr()?;
+            left = bin_expr(left, op, right);
+        }
 
-unsafe fn parse_call_mem_expr() -> Box<dyn Expr>{
-    let member = parse_mem_expr(parse_prim_expr());
-    if TOKENS[0].value_type == TokenType::LeftParen {
-        return parse_call_expr(member);
+        Ok(left)
     }
-    return member;
-}
 
-unsafe fn parse_mem_expr(mut at: Box<dyn Expr>) -> Box<dyn Expr> {
-    if TOKENS[0].value_type == TokenType::Dot {
-        TOKENS.remove(0);
-        let prop: Box<dyn Expr>;
-        prop = parse_prim_expr();
-        if !prop.as_any().downcast_ref::<Identifier>().is_some() {
-            ERROR("Right hand side of the dot operator must be an Identifier", TOKENS[0].clone());
+    fn parse_comparison_expr(&mut self) -> Result<Expr, IonError> {
+        let mut left = self.parse_additive_expr()?;
+
+        while matches!(self.peek().value.as_str(), "==" | "!=" | "<" | "<=" | ">" | ">=") && self.peek().value_type == TokenType::BinOp {
+            let op = self.advance().value;
+            let right = self.parse_additive_expr()?;
+            left = bin_expr(left, op, right);
         }
-        at = parse_mem_expr(Box::new(MemberExpr{obj: at, prop}) );
-    } else if TOKENS[0].value_type == TokenType::LeftBrace {
-        TOKENS.remove(0);
-        let prop = parse_expr();
-        expect(TokenType::RightBrace);
-        at = parse_mem_expr(Box::new(ArrMemberExpr{arr: at, index: prop}) );
+
+        Ok(left)
     }
 
-    return at;
-}
+    fn parse_additive_expr(&mut self) -> Result<Expr, IonError> {
+        if self.peek().value_type == TokenType::String && self.peek_at(1).value == "+" {
+            let mut lhs = self.parse_multiplicative_expr()?;
+            while self.peek().value == "+"{
+                let op = self.advance().value;
+                let rhs = self.parse_multiplicative_expr()?;
+                lhs = bin_expr(lhs, op, rhs);
+            }
+            return Ok(lhs);
+        }
+        let mut left = self.parse_multiplicative_expr()?;
 
-unsafe fn parse_call_expr(call_to: Box<dyn Expr>) -> Box<dyn Expr> { // accounts for xyz()()()...
-                                                                     // as well
-    let mut call_expr = Box::new(CallExpr{
-        call_to,
-        args: parse_args()
-    });
-    if TOKENS[0].value_type == TokenType::LeftParen {
-        call_expr = Box::new(parse_call_expr(call_expr).as_any().downcast_ref::<CallExpr>().unwrap().clone());
+        while self.peek().clone().value == "+" || self.peek().clone().value == "-" {
+            let op = self.advance().value;
+            let right = self.parse_multiplicative_expr()?;
+            left = bin_expr(left, op, right);
+        }
+
+        Ok(left)
     }
-    return parse_mem_expr( call_expr );
-}   
 
-unsafe fn parse_args() -> Vec<Box<dyn Expr>> { 
-    expect(TokenType::LeftParen);
-    let mut args = vec![];
-    if TOKENS[0].value_type == TokenType::RightParen {}
-    else{
-        args = parse_args_list();
+    fn parse_multiplicative_expr(&mut self) -> Result<Expr, IonError> {
+        let mut left = self.parse_call_mem_expr()?; 
+
+        while self.peek().clone().value == "*" || self.peek().clone().value == "/" || self.peek().clone().value == "%" {
+            let op = self.advance().value;
+            let right = self.parse_prim_expr()?;
+            left = bin_expr(left, op, right);
+        }
+
+        Ok(left)
     }
-    if TOKENS[0].value_type != TokenType::RightParen {
-        ERROR("Missing Closing Paren", TOKENS[0].clone());
+
+    fn parse_call_mem_expr(&mut self) -> Result<Expr, IonError> {
+        let prim = self.parse_prim_expr()?;
+        let member = self.parse_mem_expr(prim)?;
+        if self.peek().value_type == TokenType::LeftParen {
+            return self.parse_call_expr(member);
+        }
+        return Ok(member);
     }
-    TOKENS.remove(0);
-    return args;
 
-}
+    fn parse_mem_expr(&mut self, mut at: Expr) -> Result<Expr, IonError> {
+        if self.peek().value_type == TokenType::Dot {
+            self.advance();
+            let Expr::Identifier(prop) = self.parse_prim_expr()? else {
+                return Err(ERROR("Right hand side of the dot operator must be an Identifier", self.peek().clone()));
+            };
+            let span = Span::new(at.span().start, prop.span.end);
+            at = self.parse_mem_expr(MemberExpr{obj: Box::new(at), prop, span}.into())?;
+        } else if self.peek().value_type == TokenType::LeftBrace {
+            self.advance();
+            let prop = self.parse_expr()?;
+            self.expect(TokenType::RightBrace)?;
+            let span = self.span_from(at.span().start);
+            at = self.parse_mem_expr(ArrMemberExpr{arr: Box::new(at), index: Box::new(prop), span}.into())?;
+        }
 
-unsafe fn parse_args_list() -> Vec<Box<dyn Expr>> {
-    let mut args = vec![parse_expr()];
-    while TOKENS[0].value_type == TokenType::Comma {
-        TOKENS.remove(0);
-        args.push(parse_expr());
+        return Ok(at);
     }
-    return args;
-}
-
-unsafe fn parse_prim_expr() -> Box<dyn Expr> {
-    let TkType = TOKENS[0].clone();
 
-    match TkType.value_type {
-        TokenType::Identifier => {
-            Box::new(Identifier { symbol: TOKENS.remove(0).value })
+    fn parse_call_expr(&mut self, call_to: Expr) -> Result<Expr, IonError> { // accounts for xyz()()()...
+                                                                         // as well
+        let start = call_to.span().start;
+        let call_expr = CallExpr{
+            call_to: Box::new(call_to),
+            args: self.parse_args()?,
+            span: self.span_from(start),
+        };
+        if self.peek().value_type == TokenType::LeftParen {
+            return self.parse_call_expr(call_expr.into());
         }
-       TokenType::String => {
-            Box::new(Str{ content: TOKENS.remove(0).value })
+        return self.parse_mem_expr(call_expr.into());
+    }   
+
+    fn parse_args(&mut self) -> Result<Vec<Expr>, IonError> { 
+        self.expect(TokenType::LeftParen)?;
+        let mut args = vec![];
+        if self.peek().value_type == TokenType::RightParen {}
+        else{
+            args = self.parse_args_list()?;
         }
-        TokenType::Number => {
-            Box::new(NumericLiteral::<f64> {
-                value: parse_num::<f64>(TOKENS.remove(0).value.as_str()),
-            })
+        if self.peek().value_type != TokenType::RightParen {
+            return Err(ERROR("Missing Closing Paren", self.peek().clone()));
         }
-        TokenType::BinOp => {
-            if TkType.value == "-" {
-                let (lx, ly) = TOKENS[0].loc;
-                TOKENS.remove(0);
-                TOKENS.insert(0, Token{value: "*".to_string(), value_type: TokenType::BinOp, loc: (lx.clone(), ly.clone())});
-                TOKENS.insert(0, Token{value: "-1".to_string(), value_type: TokenType::Number, loc: (lx.clone(), ly.clone())});
-                return parse_expr()
-            }else{
-                ERROR("Trailing Binary Operator", TkType);
-            }
+        self.advance();
+        return Ok(args);
+
+    }
+
+    fn parse_args_list(&mut self) -> Result<Vec<Expr>, IonError> {
+        let mut args = vec![self.parse_expr()?];
+        while self.peek().value_type == TokenType::Comma {
+            self.advance();
+            args.push(self.parse_expr()?);
         }
-        TokenType::LeftParen => {
-            TOKENS.remove(0);
-            let value = parse_expr();
-            if TOKENS[0].value != ")" {
-                ERROR("Missing Closing Paren", TOKENS[0].clone());
+        return Ok(args);
+    }
+
+    fn parse_prim_expr(&mut self) -> Result<Expr, IonError> {
+        let TkType = self.peek().clone();
+
+        match TkType.value_type {
+            TokenType::Identifier => {
+                let tok = self.advance();
+                Ok(Identifier { span: token_span(&tok), symbol: tok.value }.into())
+            }
+           TokenType::String => {
+                let tok = self.advance();
+                Ok(Str{ span: token_span(&tok), content: tok.value }.into())
+            }
+            TokenType::Number => {
+                let tok = self.advance();
+                Ok(NumericLiteral {
+                    value: parse_number(&tok)?,
+                    span: token_span(&tok),
+                }.into())
+            }
+            TokenType::BinOp => {
+                if TkType.value == "!" {
+                    self.advance();
+                    let operand = self.parse_call_mem_expr()?;
+                    return Ok(UnaryExpr{operand: Box::new(operand), operator: TkType.value, span: self.span_from(TkType.loc)}.into());
+                }
+                if TkType.value == "-" {
+                    let (lx, ly) = self.peek().loc;
+                    self.advance();
+                    self.tokens.insert(self.pos, Token{value: "*".to_string(), value_type: TokenType::BinOp, loc: (lx.clone(), ly.clone())});
+                    self.tokens.insert(self.pos, Token{value: "-1".to_string(), value_type: TokenType::Number, loc: (lx.clone(), ly.clone())});
+                    return self.parse_expr()
+                }else{
+                    Err(ERROR("Trailing Binary Operator", TkType))
+                }
+            }
+            TokenType::LeftParen => {
+                self.advance();
+                let value = self.parse_expr()?;
+                if self.peek().value != ")" {
+                    return Err(ERROR("Missing Closing Paren", self.peek().clone()));
+                }
+                self.advance();
+                return Ok(value);
+            }
+            TokenType::Nil_k => {
+                Ok(Nil { span: token_span(&self.advance()) }.into())
+            }
+            TokenType::Bool_true_t => {
+                Ok(Bool { value: true, span: token_span(&self.advance()) }.into())
+            }
+            TokenType::Bool_false_t => {
+                Ok(Bool { value: false, span: token_span(&self.advance()) }.into())
+            }
+            _ => {
+                Err(ERROR(&format!("Unexpected Token: {:?}; Cannot be parsed as an expression", TkType), TkType))
             }
-            TOKENS.remove(0);
-            return value;
-        }
-        TokenType::Nil_k => {
-            TOKENS.remove(0);
-            Box::new(Nil {})
         }
-        TokenType::Bool_true_t => {
-            TOKENS.remove(0);
-            Box::new(Bool { value: true })
+    }
+
+
+    fn end_stmt(&mut self) -> Result<(), IonError> {
+        if self.peek().value_type == TokenType::Semicolon{
+            self.advance();
+            Ok(())
+        }else{
+            Err(ERROR(&format!("Statement must end with a [ ; ] | Current terminating token [ {:?} ]", self.peek()), self.peek().clone())
+                .with_help("add a [ ; ] at the end of the previous statement"))
         }
-        TokenType::Bool_false_t => {
-            TOKENS.remove(0);
-            Box::new(Bool { value: false })
+    }
+
+    fn expect(&mut self, tok: TokenType) -> Result<Token, IonError> {
+        if self.peek().value_type != tok {
+            return Err(ERROR(&format!("Expected {:?}, found {}", tok, self.peek().value), self.peek().clone()));
         }
-        _ => {
-            ERROR(&format!("Unexpected Token: {:?}; Cannot be parsed as an expression", TkType), TkType);
+        Ok(self.advance())
+    }
+
+    fn advance(&mut self) -> Token {
+        let tok = self.peek().clone();
+        // The trailing EOF token is never consumed, so peeking stays in bounds
+        if tok.value_type != TokenType::EOF {
+            self.pos += 1;
         }
+        self.prev_end = token_span(&tok).end;
+        tok
     }
-}
 
+    fn span_from(&mut self, start: (isize, isize)) -> Span {
+        Span::new(start, self.prev_end)
+    }
+}
 
-pub fn parse_num<T>(s: &str) -> T
+pub fn parse_num<T>(tok: &Token) -> Result<T, IonError>
 where
     T: Num + FromStr + Copy,
-    <T as FromStr>::Err: Debug
 {
-    T::from_str(s).unwrap()
+    T::from_str(&tok.value).map_err(|_| ERROR(&format!("Invalid number [ {} ]", tok.value), tok.clone()))
 }
 
-unsafe fn end_stmt(){
-    if TOKENS[0].value_type == TokenType::Semicolon{
-        TOKENS.remove(0);
-    }else{
-        ERROR(&format!("Statement must end with a [ ; ] | Current terminating token [ {:?} ]", TOKENS[0]), TOKENS[0].clone());
+// Whole numbers stay exact; ones too long even for an i128 are read as floats, like fractions
+pub fn parse_number(tok: &Token) -> Result<Number, IonError> {
+    match parse_num::<i128>(tok) {
+        Ok(n) => Ok(Number::Int(n)),
+        Err(_) => parse_num::<f64>(tok).map(Number::Float),
     }
 }
 
-unsafe fn expect(tok: TokenType) -> Token {
-    if TOKENS[0].value_type != tok {
-        ERROR(&format!("Expected {:?}, found {}", tok, TOKENS[0].value), TOKENS[0].clone());
-    }
-    TOKENS.remove(0)
+fn token_span(tok: &Token) -> Span {
+    let len = tok.value.chars().count().max(1) as isize;
+    Span::new(tok.loc, (tok.loc.0, tok.loc.1 + len - 1))
+}
+
+fn bin_expr(left: Expr, operator: String, right: Expr) -> Expr {
+    let span = Span::new(left.span().start, right.span().end);
+    BinExpr { left: Box::new(left), right: Box::new(right), operator, span }.into()
 }
 
-unsafe fn ERROR(msg: &str, tok: Token) -> !{
-    println!("(At {}:{})", tok.loc.0, tok.loc.1);
-    panic!("{}", msg);
+fn ERROR(msg: &str, tok: Token) -> IonError {
+    IonError::parse(msg, token_span(&tok))
 }
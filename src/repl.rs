@@ -0,0 +1,106 @@
+use std::io::{self, BufRead, Write};
+
+use ion::error::IonError;
+use ion::values::RuntimeValueType;
+use ion::{diagnostic, parser, Interpreter};
+
+const PROMPT: &str = "ion> ";
+const CONTINUE_PROMPT: &str = "...> ";
+
+// Reads statements until EOF (or `:quit`), evaluating each input in one long-lived global scope.
+pub fn run(args: Vec<String>) {
+    let mut interpreter = Interpreter::with_args(args);
+    let stdin = io::stdin();
+    let mut input = String::new();
+
+    loop {
+        print!("{}", if input.is_empty() { PROMPT } else { CONTINUE_PROMPT });
+        io::stdout().flush().ok();
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) | Err(_) => {
+                println!();
+                return;
+            },
+            Ok(_) => {},
+        }
+        if input.is_empty() && matches!(line.trim(), ":q" | ":quit") {
+            return;
+        }
+        input += &line;
+
+        // Keep reading while a `{`, `[` or `(` is left open
+        if open_delimiters(&input) > 0 {
+            continue;
+        }
+        let source = std::mem::take(&mut input);
+        if source.trim().is_empty() {
+            continue;
+        }
+
+        match eval_input(&source, &mut interpreter) {
+            Ok(Some(output)) => println!("{}", output),
+            Ok(None) => {},
+            Err(err) => eprintln!("{}", diagnostic::render(&err, "<repl>", &source)),
+        }
+    }
+}
+
+fn eval_input(source: &str, interpreter: &mut Interpreter) -> Result<Option<String>, IonError> {
+    // A lone statement doesn't need its trailing `;` at the prompt
+    let source = source.trim_end().to_string();
+    let program = match parser::prod_ast(source.clone()) {
+        Ok(program) => program,
+        Err(err) if !source.ends_with(';') => parser::prod_ast(source + ";").map_err(|_| err)?,
+        Err(err) => return Err(err),
+    };
+    let value = interpreter.eval(program)?;
+    match value.Type() {
+        RuntimeValueType::StmtExec | RuntimeValueType::Nil => Ok(None),
+        _ => Ok(Some(value.to_string())),
+    }
+}
+
+// Net count of unclosed delimiters, ignoring those inside strings and comments.
+fn open_delimiters(source: &str) -> isize {
+    let mut depth = 0;
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth -= 1,
+            '"' => {
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                }
+            },
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            },
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let (mut prev, mut closed) = (' ', false);
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        closed = true;
+                        break;
+                    }
+                    prev = c;
+                }
+                // An unterminated block comment also keeps the input open
+                if !closed {
+                    depth += 1;
+                }
+            },
+            _ => {},
+        }
+    }
+    depth
+}
@@ -30,7 +30,7 @@ impl fmt::Display for ArrayLiteralVal {
 
 impl fmt::Display for FuncStructVal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let params: Vec<String> = self.parameters.keys().cloned().collect();
+        let params: Vec<String> = self.parameters.iter().map(|p| p.param.clone()).collect();
         write!(f, "fn({}) -> {:?}", params.join(", "), self.return_type)
     }
 }
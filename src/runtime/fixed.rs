@@ -0,0 +1,221 @@
+use crate::ast::Number;
+use crate::error::IonError;
+use crate::interpreter::RuntimeValueServe;
+use crate::lexer::{Attr, Flags, NumKind, Overflow};
+use crate::values::{FixedVal, NumericVal, RuntimeValue};
+
+// Fixed-width numbers: a `<structure: u8>` variable holds a `FixedVal` of that width, and arithmetic
+// with one on either side stays in the width instead of being minimized again. Results that don't
+// fit follow the `<overflow: ..>` policy of the fixed operand (the left one when both are):
+// `checked` (the default) fails, `wrapping` wraps around like Rust's `wrapping_*`, `saturating`
+// clamps. Floats follow IEEE arithmetic; overflowing to infinity counts as not fitting.
+
+// The values an integer type holds
+fn bounds(kind: NumKind) -> Option<(i128, i128)> {
+    let bounds = match kind {
+        NumKind::U8 => (0, u8::MAX as i128),
+        NumKind::U16 => (0, u16::MAX as i128),
+        NumKind::U32 => (0, u32::MAX as i128),
+        NumKind::U64 => (0, u64::MAX as i128),
+        NumKind::I8 => (i8::MIN as i128, i8::MAX as i128),
+        NumKind::I16 => (i16::MIN as i128, i16::MAX as i128),
+        NumKind::I32 => (i32::MIN as i128, i32::MAX as i128),
+        NumKind::I64 => (i64::MIN as i128, i64::MAX as i128),
+        NumKind::F32 | NumKind::F64 => return None,
+    };
+    Some(bounds)
+}
+
+fn wrap(n: i128, kind: NumKind) -> i128 {
+    match kind {
+        NumKind::U8 => n as u8 as i128,
+        NumKind::U16 => n as u16 as i128,
+        NumKind::U32 => n as u32 as i128,
+        NumKind::U64 => n as u64 as i128,
+        NumKind::I8 => n as i8 as i128,
+        NumKind::I16 => n as i16 as i128,
+        NumKind::I32 => n as i32 as i128,
+        NumKind::I64 => n as i64 as i128,
+        NumKind::F32 | NumKind::F64 => n,
+    }
+}
+
+macro_rules! number_of {
+    ($any:expr, ints: [$($int:ty),*], floats: [$($float:ty),*]) => {
+        $(
+            if let Some(n) = $any.downcast_ref::<NumericVal<$int>>() {
+                return Some(Number::Int(n.value as i128));
+            }
+        )*
+        $(
+            if let Some(n) = $any.downcast_ref::<NumericVal<$float>>() {
+                return Some(Number::Float(n.value as f64));
+            }
+        )*
+    };
+}
+
+pub fn number_of(value: &dyn RuntimeValue) -> Option<Number> {
+    let any = value.as_any();
+    if let Some(fixed) = any.downcast_ref::<FixedVal>() {
+        return Some(fixed.value);
+    }
+    number_of!(any, ints: [u8, u16, u32, u64, i8, i16, i32, i64], floats: [f32, f64]);
+    None
+}
+
+pub fn overflow_policy(flags: &[Flags]) -> Overflow {
+    flags.iter().find_map(|flag| match flag {
+        Flags::Overflow_f(policy) => Some(*policy),
+        _ => None,
+    }).unwrap_or_default()
+}
+
+// `value` as a `kind`, if it is a number that fits in one
+pub fn fixed_value(value: &dyn RuntimeValue, kind: NumKind, overflow: Overflow) -> Result<FixedVal, IonError> {
+    let number = number_of(value).ok_or_else(|| {
+        IonError::typing(format!("Incorrect Type Assignement | Expected: {} | Found: {}", kind.name(), value))
+    })?;
+    let out_of_range = || IonError::typing(format!("Value {} is out of range for <structure: {}>", value, kind.name()));
+
+    let value = match (number, bounds(kind)) {
+        (Number::Int(n), Some((min, max))) => {
+            if n < min || n > max {
+                return Err(out_of_range().with_help(format!("<structure: {}> holds {}..={}", kind.name(), min, max)));
+            }
+            Number::Int(n)
+        },
+        (Number::Float(n), Some((min, max))) => {
+            if n.fract() != 0.0 {
+                return Err(IonError::typing(format!("Value {} is not an integer, as <structure: {}> requires", value, kind.name())));
+            }
+            if n < min as f64 || n > max as f64 {
+                return Err(out_of_range().with_help(format!("<structure: {}> holds {}..={}", kind.name(), min, max)));
+            }
+            Number::Int(n as i128)
+        },
+        (number, None) => {
+            let n = match number {
+                Number::Int(n) => n as f64,
+                Number::Float(n) => n,
+            };
+            if kind == NumKind::F32 && n.is_finite() && n.abs() > f32::MAX as f64 {
+                return Err(out_of_range());
+            }
+            Number::Float(if kind == NumKind::F32 { n as f32 as f64 } else { n })
+        },
+    };
+    Ok(FixedVal{value, kind, overflow})
+}
+
+// What a variable declared (or a parameter / result typed) with `attr` stores for `value`:
+// a `FixedVal` when `attr` is a fixed-width type, None when the value is stored as is
+pub fn conform(value: &dyn RuntimeValue, attr: &Attr, flags: &[Flags]) -> Result<Option<FixedVal>, IonError> {
+    match attr {
+        Attr::Fixed(kind) if number_of(value).is_some() => fixed_value(value, *kind, overflow_policy(flags)).map(Some),
+        _ => Ok(None),
+    }
+}
+
+// Arithmetic with a fixed-width operand; None when neither operand is one
+pub fn eval_fixed_bin_expr(lhs: &dyn RuntimeValue, rhs: &dyn RuntimeValue, op: &str) -> Option<Result<RuntimeValueServe, IonError>> {
+    let (l, r) = (lhs.as_any().downcast_ref::<FixedVal>(), rhs.as_any().downcast_ref::<FixedVal>());
+    let (kind, overflow) = match (l, r) {
+        (Some(l), Some(r)) if l.kind != r.kind => {
+            let err = IonError::typing(format!("Mismatched numeric types | Found: {} {} {}", l.kind.name(), op, r.kind.name()))
+                .with_help("declare both operands with the same <structure>");
+            return Some(Err(err));
+        },
+        (Some(fixed), _) | (None, Some(fixed)) => (fixed.kind, fixed.overflow),
+        (None, None) => return None,
+    };
+    let result = match bounds(kind) {
+        Some(bounds) => int_arithmetic(lhs, rhs, op, kind, bounds, overflow),
+        None => float_arithmetic(lhs, rhs, op, kind, overflow),
+    };
+    Some(result.map(|value| RuntimeValueServe::Owned(Box::new(FixedVal{value, kind, overflow}))))
+}
+
+fn int_operand(value: &dyn RuntimeValue, op: &str, kind: NumKind) -> Result<i128, IonError> {
+    match number_of(value) {
+        Some(Number::Int(n)) => Ok(n),
+        Some(Number::Float(n)) if n.fract() == 0.0 && n.abs() < i128::MAX as f64 => Ok(n as i128),
+        _ => Err(IonError::typing(format!("Operator [ {} ] on a {} needs an integer operand | Found: {}", op, kind.name(), value))),
+    }
+}
+
+fn int_arithmetic(lhs: &dyn RuntimeValue, rhs: &dyn RuntimeValue, op: &str, kind: NumKind, (min, max): (i128, i128), overflow: Overflow) -> Result<Number, IonError> {
+    let a = int_operand(lhs, op, kind)?;
+    let b = int_operand(rhs, op, kind)?;
+    let (exact, wrapped) = match op {
+        "+" => (a.checked_add(b), a.wrapping_add(b)),
+        "-" => (a.checked_sub(b), a.wrapping_sub(b)),
+        "*" => (a.checked_mul(b), a.wrapping_mul(b)),
+        "/" => {
+            if b == 0 {
+                return Err(IonError::runtime("Division by zero"));
+            }
+            (a.checked_div(b), a.wrapping_div(b))
+        },
+        "%" => {
+            if b == 0 {
+                return Err(IonError::runtime("Modulo by zero"));
+            }
+            (a.checked_rem(b), a.wrapping_rem(b))
+        },
+        _ => return Err(IonError::runtime(format!("Invalid operator: {}", op))),
+    };
+
+    let n = match (exact, overflow) {
+        (Some(n), _) if n >= min && n <= max => n,
+        (_, Overflow::Checked) => {
+            return Err(IonError::runtime(format!("Arithmetic overflow: {} {} {} doesn't fit in a {}", a, op, b, kind.name()))
+                .with_help("declare the variable with `<overflow: wrapping>` or `<overflow: saturating>` to allow it"));
+        },
+        (_, Overflow::Wrapping) => wrap(wrapped, kind),
+        (Some(n), Overflow::Saturating) => n.clamp(min, max),
+        // Only products of 64 bit values overflow an i128; their sign tells which end to clamp to
+        (None, Overflow::Saturating) => if (a < 0) != (b < 0) { min } else { max },
+    };
+    Ok(Number::Int(n))
+}
+
+fn float_arithmetic(lhs: &dyn RuntimeValue, rhs: &dyn RuntimeValue, op: &str, kind: NumKind, overflow: Overflow) -> Result<Number, IonError> {
+    let float = |value: &dyn RuntimeValue| match number_of(value) {
+        Some(Number::Int(n)) => Ok(n as f64),
+        Some(Number::Float(n)) => Ok(n),
+        None => Err(IonError::typing(format!("Operator [ {} ] on a {} needs a numeric operand | Found: {}", op, kind.name(), value))),
+    };
+    let (a, b) = (float(lhs)?, float(rhs)?);
+    let n = match op {
+        "+" => a + b,
+        "-" => a - b,
+        "*" => a * b,
+        "/" => {
+            if b == 0.0 {
+                return Err(IonError::runtime("Division by zero"));
+            }
+            a / b
+        },
+        "%" => {
+            if b == 0.0 {
+                return Err(IonError::runtime("Modulo by zero"));
+            }
+            a % b
+        },
+        _ => return Err(IonError::runtime(format!("Invalid operator: {}", op))),
+    };
+
+    let (n, max) = match kind {
+        NumKind::F32 => (n as f32 as f64, f32::MAX as f64),
+        _ => (n, f64::MAX),
+    };
+    if n.is_infinite() && a.is_finite() && b.is_finite() {
+        return match overflow {
+            Overflow::Checked => Err(IonError::runtime(format!("Arithmetic overflow: {} {} {} doesn't fit in a {}", a, op, b, kind.name()))),
+            Overflow::Saturating => Ok(Number::Float(max.copysign(n))),
+            Overflow::Wrapping => Ok(Number::Float(n)),
+        };
+    }
+    Ok(Number::Float(n))
+}
@@ -1,11 +1,15 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
+use std::rc::Rc;
 
-use crate::ast::{self, ArrMemberExpr, Array, ArrayLiteral, BinExpr, CallExpr, FnStruct, Identifier, MemberExpr, NodeType, NumericLiteral, Object, ObjectLiteral, Program, Stmt, Str, VarAsg, VarDeclaration};
-use crate::lexer::Attr;
-use crate::scopes::Scope;
-use crate::values::{BooleanVal, FuncStructVal, NativeFnValue, NilVal, NumericVal, ObjectLiteralVal, ObjectVal, RuntimeValue, RuntimeValueType, StmtExecS};
+use crate::ast::{ArrMemberExpr, Expr, Array, ArrayLiteral, BinExpr, CallExpr, FnStruct, ForStmt, Identifier, IfStmt, MemberExpr, Number, Object, ObjectLiteral, Program, RangeExpr, Return, Stmt, Str, UnaryExpr, VarAsg, VarDeclaration, WhileStmt};
+use crate::diagnostic::suggest;
+use crate::lexer::{Attr, Flags, Overflow, ATTR_NAMES};
+use crate::error::{IonError, Span};
+use crate::scopes::{Parent, Scope};
+use crate::fixed;
+use crate::values::{BooleanVal, BreakSignal, ContinueSignal, FixedVal, FuncStructVal, NativeFnValue, NilVal, NumericVal, ObjectLiteralVal, ObjectVal, RangeVal, ReturnSignal, RuntimeValue, RuntimeValueType, StmtExecS};
 
 use super::values::{ArrayLiteralVal, ArrayVal, StrLiteral};
 
@@ -14,7 +18,7 @@ macro_rules! extract_numeric {
         $(
             if let Some(n) = $any.downcast_ref::<NumericVal<$ty>>() {
                 let val: $cast_to = n.value as $cast_to;
-                return val;
+                return Ok(val);
             }
         )*
     };
@@ -50,113 +54,85 @@ impl Clone for RuntimeValueServe {
 }
 
 
-pub fn evaluate<'a>(astnode: Box<dyn Stmt>, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    match astnode.kind() {
-        ast::NodeType::NumericLiteralNode => {
-            let val = astnode
-                .as_any()
-                .downcast_ref::<NumericLiteral<f64>>() // Still parsing as f64
-                .unwrap()
-                .value;
-
-            let minimized = minimize_numeric(val); 
-
-            let boxed: Box<dyn RuntimeValue> = match minimized {
-                MinimizedNumeric::I8(v) => Box::new(v),
-                MinimizedNumeric::I16(v) => Box::new(v),
-                MinimizedNumeric::I32(v) => Box::new(v),
-                MinimizedNumeric::I64(v) => Box::new(v),
-                MinimizedNumeric::U8(v) => Box::new(v),
-                MinimizedNumeric::U16(v) => Box::new(v),
-                MinimizedNumeric::U32(v) => Box::new(v),
-                MinimizedNumeric::U64(v) => Box::new(v),
-                MinimizedNumeric::F32(v) => Box::new(v),
-                MinimizedNumeric::F64(v) => Box::new(v),
-            };
-
-            RuntimeValueServe::Owned(boxed)
-        }
-        ast::NodeType::Identifier => {
-            eval_identifier(astnode.as_any().downcast_ref::<Identifier>().unwrap(), scope)
+pub fn evaluate(stmt: &Stmt, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    // Errors are pinned to the innermost node that raised them
+    let result = match stmt {
+        Stmt::VarDecl(node) => eval_var_decl(node, scope),
+        Stmt::VarAsg(node) => eval_var_asg(node, scope),
+        Stmt::Return(node) => eval_return(node, scope),
+        Stmt::If(node) => eval_if(node, scope),
+        Stmt::While(node) => eval_while(node, scope),
+        Stmt::For(node) => eval_for(node, scope),
+        Stmt::Break(_) => Ok(RuntimeValueServe::Owned(Box::new(BreakSignal {}))),
+        Stmt::Continue(_) => Ok(RuntimeValueServe::Owned(Box::new(ContinueSignal {}))),
+        Stmt::Expr(expr) => return eval_expr(expr, scope),
+    };
+    result.map_err(|err| err.or_span(stmt.span()))
+}
 
-        }
-        ast::NodeType::String => {
-            eval_string(astnode.as_any().downcast_ref::<Str>().unwrap(), scope)
+pub fn eval_expr(expr: &Expr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let result = match expr {
+        Expr::Numeric(node) => number_value(node.value).map(RuntimeValueServe::Owned),
+        Expr::Str(node) => eval_string(node, scope),
+        Expr::Identifier(node) => eval_identifier(node, scope),
+        Expr::Nil(_) => Ok(RuntimeValueServe::Owned(Box::new(NilVal {}))),
+        Expr::Bool(node) => Ok(RuntimeValueServe::Owned(Box::new(BooleanVal { val: node.value }))),
+        Expr::Binary(node) => eval_bin_expr(node, scope),
+        Expr::Unary(node) => eval_unary_expr(node, scope),
+        Expr::Range(node) => eval_range(node, scope),
+        Expr::Object(node) => eval_obj_expr(node, scope),
+        Expr::ObjectLiteral(node) => eval_obj_literal_expr(node, scope),
+        Expr::Array(node) => eval_array_expr(node),
+        Expr::ArrayLiteral(node) => eval_array_literal_expr(node, scope),
+        Expr::Member(node) => eval_membr_expr(node, scope),
+        Expr::ArrMember(node) => eval_arr_membr_expr(node, scope),
+        Expr::FnStruct(node) => eval_fn_struct(node, scope),
+        Expr::Call(node) => eval_call_expr(node, scope),
+    };
+    result.map_err(|err| err.or_span(expr.span()))
+}
 
-        }
-        ast::NodeType::Nil => RuntimeValueServe::Owned(Box::new(NilVal {})),
-        ast::NodeType::Bool => RuntimeValueServe::Owned(Box::new(BooleanVal {
-            val: astnode
-                .as_any()
-                .downcast_ref::<ast::Bool>()
-                .unwrap()
-                .value,
-        })),
-        ast::NodeType::BinOp => {
-            eval_bin_expr(astnode.as_any().downcast_ref::<BinExpr>().unwrap(), scope)
-        },
-        ast::NodeType::Object => {
-            eval_obj_expr(astnode.as_any().downcast_ref::<Object>().unwrap(), scope)
-        },
-        ast::NodeType::ObjectLiteral => {
-            eval_obj_literal_expr(astnode.as_any().downcast_ref::<ObjectLiteral>().unwrap(), scope)
-        },
-        ast::NodeType::Array => {
-            eval_array_expr(astnode.as_any().downcast_ref::<Array>().unwrap(), scope)
-        },
-        ast::NodeType::ArrayLiteral => {
-            eval_array_literal_expr(astnode.as_any().downcast_ref::<ArrayLiteral>().unwrap(), scope)
-        }
-        ast::NodeType::FnStruct => {
-            eval_fn_struct(astnode.as_any().downcast_ref::<FnStruct>().unwrap(), scope)
-        },
-        ast::NodeType::CallExpr => {
-            eval_call_expr(astnode.as_any().downcast_ref::<CallExpr>().unwrap(), scope)
-        }
-        ast::NodeType::Program => eval_program(astnode, scope),
-        ast::NodeType::VarDecl => {
-            eval_var_decl(astnode.as_any().downcast_ref::<VarDeclaration>().unwrap(), scope)
-        },
-        ast::NodeType::VarAsg => {
-            eval_var_asg(astnode.as_any().downcast_ref::<VarAsg>().unwrap(), scope)
-        },
-        ast::NodeType::MemberExpr => {
-            eval_membr_expr(astnode.as_any().downcast_ref::<MemberExpr>().unwrap(), scope)
-        },
-        ast::NodeType::ArrMemberExpr => {
-            eval_arr_membr_expr(astnode.as_any().downcast_ref::<ArrMemberExpr>().unwrap(), scope)
-        },
-        _ => panic!("Can't evaluate AST node yet: {:?}", astnode),
+// Identifiers are stored by reference so the variable they name is resolved when the value is used
+fn eval_stored(expr: &Expr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    match expr {
+        Expr::Identifier(ident) => Ok(RuntimeValueServe::Ref(ident.clone())),
+        _ => eval_expr(expr, scope),
     }
 }
 
-fn eval_arr_membr_expr(unwrap: &ArrMemberExpr, scope: &'static RefCell<Scope>) -> RuntimeValueServe {    
-    let arr_eval = evaluate(unwrap.arr.clone(), scope);
-    let i_shell = evaluate(unwrap.clone().index, scope);
+fn eval_arr_membr_expr(unwrap: &ArrMemberExpr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {    
+    let arr_eval = eval_expr(&unwrap.arr, scope)?;
+    let i_shell = eval_expr(&unwrap.index, scope)?;
     
-    let i = extract_as_i64(i_shell) as usize;
+    let i = extract_as_i128(i_shell)?;
 
     match arr_eval {
         RuntimeValueServe::Owned(arr_val) => {
-            let arr_val = unwrap_runtime_value_serve(RuntimeValueServe::Owned(arr_val), scope);
-            let arr = arr_val.as_any().downcast_ref::<ArrayLiteralVal>().expect("Array indexing can only be executed on an array.");
-            let i_val = arr.entries[i].clone();
-            i_val
+            let arr_val = unwrap_runtime_value_serve(RuntimeValueServe::Owned(arr_val), scope)?;
+            let arr = arr_val.as_any().downcast_ref::<ArrayLiteralVal>()
+                .ok_or_else(|| IonError::typing(format!("Array indexing can only be executed on an array | Found: {}", arr_val)))?;
+            let i_val = usize::try_from(i).ok().and_then(|i| arr.entries.get(i))
+                .ok_or_else(|| {
+                    let err = IonError::runtime(format!("Index {} is out of bounds for an array of length {}", i, arr.entries.len()));
+                    if i < 0 { err.with_help("array indexes start at 0") } else { err }
+                })?
+                .clone();
+            Ok(i_val)
         },
         _ => {
-            panic!("Cannot work with raw REF in runtime...");
+            Err(IonError::runtime("Cannot work with raw REF in runtime..."))
         }   
     }
 }
 
-fn eval_string(unwrap: &Str, _scope: &RefCell<Scope>) -> RuntimeValueServe {
-    RuntimeValueServe::Owned(Box::new(StrLiteral{content: unwrap.content.clone()}))
+fn eval_string(unwrap: &Str, _scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    Ok(RuntimeValueServe::Owned(Box::new(StrLiteral{content: unwrap.content.clone()})))
 }
 
-fn eval_array_literal_expr(unwrap: &ArrayLiteral, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
+fn eval_array_literal_expr(unwrap: &ArrayLiteral, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
     let mut arr = ArrayLiteralVal { entries: vec![] };
     for entry in &unwrap.entries {
-        let val = evaluate(  entry.clone() , scope); 
+        let val = eval_expr(entry, scope)?; 
         if let RuntimeValueServe::Owned(_) = val.clone(){
             arr.entries.push(val.clone());
         }else if let RuntimeValueServe::Ref(_) = val.clone(){
@@ -164,225 +140,409 @@ fn eval_array_literal_expr(unwrap: &ArrayLiteral, scope: &'static RefCell<Scope>
         }
 
     }
-    RuntimeValueServe::Owned(Box::new(arr))
+    Ok(RuntimeValueServe::Owned(Box::new(arr)))
 }
 
-fn eval_array_expr(unwrap: &Array, scope: &RefCell<Scope>) -> RuntimeValueServe {
+fn eval_array_expr(unwrap: &Array) -> Result<RuntimeValueServe, IonError> {
     let arr = match &unwrap.complex_attr {
         Some(str) => {
-            ArrayVal { attr: unwrap.attr.clone(), complex: Some(Identifier{symbol: str.to_string()}), length: unwrap.length }
+            ArrayVal { attr: unwrap.attr.clone(), complex: Some(Identifier{symbol: str.to_string(), span: Span::default()}), length: unwrap.length }
         },
         None => ArrayVal { attr: unwrap.attr.clone(), complex: None, length: unwrap.length }
     };
-    RuntimeValueServe::Owned(Box::new(arr))
+    Ok(RuntimeValueServe::Owned(Box::new(arr)))
 }
 
-fn eval_membr_expr(unwrap: &MemberExpr, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    let obj_eval = evaluate(unwrap.obj.clone(), scope);
-    let prop_name = &unwrap.prop.as_any().downcast_ref::<Identifier>().unwrap().symbol;
+fn eval_membr_expr(unwrap: &MemberExpr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let obj_eval = eval_expr(&unwrap.obj, scope)?;
+    let prop_name = &unwrap.prop.symbol;
 
     match obj_eval {
         RuntimeValueServe::Owned(obj_val) => {
-            let obj_val = unwrap_runtime_value_serve(RuntimeValueServe::Owned(obj_val), scope);
-            let obj = obj_val.as_any().downcast_ref::<ObjectLiteralVal>().unwrap();
+            let obj_val = unwrap_runtime_value_serve(RuntimeValueServe::Owned(obj_val), scope)?;
+            let obj = obj_val.as_any().downcast_ref::<ObjectLiteralVal>()
+                .ok_or_else(|| IonError::typing(format!("Property access can only be executed on an object | Found: {}", obj_val)))?;
             let prop_val = obj.properties.get(prop_name)
-                .unwrap_or_else(|| panic!("Property '{}' not found", prop_name))
+                .ok_or_else(|| IonError::name(format!("Property '{}' not found", prop_name)))?
                 .clone();
-            prop_val
+            Ok(prop_val)
         },
         _ => {
-            panic!("Cannot work with raw REF in runtime...");
+            Err(IonError::runtime("Cannot work with raw REF in runtime..."))
         }   
     }
 }
 
-fn eval_call_expr<'a>(unwrap: &CallExpr, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    let args = unwrap.args.iter().map(|a| evaluate(a.clone(), scope) ).collect();
-    let func = unwrap_runtime_value_serve( evaluate(unwrap.call_to.clone() , scope), scope);
+fn eval_call_expr(unwrap: &CallExpr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let args = unwrap.args.iter().map(|a| eval_expr(a, scope)).collect::<Result<Vec<_>, _>>()?;
+    let func = unwrap_runtime_value_serve(eval_expr(&unwrap.call_to, scope)?, scope)?;
 
     if func.Type() == RuntimeValueType::NativeFn {
         let result = func.as_any().downcast_ref::<NativeFnValue>().unwrap().call.call_fn(args, scope);
         return result;
     }
-    todo!();
+    if func.Type() == RuntimeValueType::FnStructVal {
+        return call_fn_struct(func.as_any().downcast_ref::<FuncStructVal>().unwrap(), args, scope);
+    }
+    Err(IonError::typing(format!("Expression [ {} ] is not callable", func)))
+}
+
+fn call_fn_struct(func: &FuncStructVal, args: Vec<RuntimeValueServe>, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    if args.len() != func.parameters.len() {
+        return Err(IonError::runtime(format!("Function [ {} ] expected {} argument(s), found {}", func, func.parameters.len(), args.len())));
+    }
+
+    let fn_scope = Rc::new(RefCell::new(Scope::new(Parent::Scope(Rc::clone(&func.closure)))));
+    for (param, arg) in func.parameters.iter().zip(args) {
+        let value = unwrap_runtime_value_serve(arg, scope)?;
+        attr_type_check(value.clone(), &param.param_type, &fn_scope)?;
+        let flags = match &param.param_type {
+            Attr::Complex(_) => vec![Flags::Struct_f(Attr::ComplexKind), Flags::Complex_f(param.param_type.clone())],
+            attr => vec![Flags::Struct_f(attr.clone())],
+        };
+        let value: Box<dyn RuntimeValue> = match fixed::conform(value.as_ref(), &param.param_type, &flags)? {
+            Some(fixed) => Box::new(fixed),
+            None => value,
+        };
+        fn_scope.borrow_mut().var_decl(param.param.clone(), RuntimeValueServe::Owned(value), flags)?;
+    }
+
+    let result = match eval_block(&func.body, &fn_scope)? {
+        RuntimeValueServe::Owned(v) if v.Type() == RuntimeValueType::Return => {
+            v.as_any().downcast_ref::<ReturnSignal>().unwrap().value.clone()
+        },
+        other => {
+            if let Some(signal) = control_signal(&other) {
+                return Err(misplaced_signal(signal));
+            }
+            other
+        },
+    };
+
+    let mut result = unwrap_runtime_value_serve(pin_refs(result, &fn_scope, Some(&func.closure))?, &fn_scope)?;
+    if result.Type() == RuntimeValueType::StmtExec {
+        result = Box::new(NilVal {});
+    }
+    attr_type_check(result.clone(), &func.return_type, &fn_scope)?;
+    if let Some(fixed) = fixed::conform(result.as_ref(), &func.return_type, &[])? {
+        result = Box::new(fixed);
+    }
+    Ok(RuntimeValueServe::Owned(result))
+}
+
+fn eval_return(unwrap: &Return, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    // The value outlives the function's scope, so nothing in it may still name a local
+    let value = pin_refs(eval_expr(&unwrap.value, scope)?, scope, None)?;
+    Ok(RuntimeValueServe::Owned(Box::new(ReturnSignal { value })))
+}
+
+fn eval_if(unwrap: &IfStmt, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let condition = unwrap_runtime_value_serve(eval_expr(&unwrap.condition, scope)?, scope)?;
+    let condition = condition.as_any().downcast_ref::<BooleanVal>().ok_or_else(|| {
+        IonError::typing(format!("Condition of [ if ] must be of type bool | Found: {}", condition))
+    })?.val;
+
+    let branch = if condition { Some(&unwrap.body) } else { unwrap.alternate.as_ref() };
+    if let Some(body) = branch {
+        let block_scope = Rc::new(RefCell::new(Scope::new(Parent::Scope(Rc::clone(scope)))));
+        let result = eval_block(body, &block_scope)?;
+        if is_control_signal(&result) {
+            return Ok(result);
+        }
+    }
+    Ok(RuntimeValueServe::Owned(Box::new(StmtExecS {})))
+}
+
+fn eval_block(body: &[Stmt], scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let mut result = RuntimeValueServe::Owned(Box::new(NilVal {}));
+    for stmt in body {
+        result = evaluate(stmt, scope)?;
+        if is_control_signal(&result) {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+fn eval_while(unwrap: &WhileStmt, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    loop {
+        let condition = unwrap_runtime_value_serve(eval_expr(&unwrap.condition, scope)?, scope)?;
+        let condition = condition.as_any().downcast_ref::<BooleanVal>().ok_or_else(|| {
+            IonError::typing(format!("Condition of [ while ] must be of type bool | Found: {}", condition))
+        })?.val;
+        if !condition {
+            break;
+        }
+
+        let block_scope = Rc::new(RefCell::new(Scope::new(Parent::Scope(Rc::clone(scope)))));
+        let result = eval_block(&unwrap.body, &block_scope)?;
+        match control_signal(&result) {
+            Some(RuntimeValueType::Break) => break,
+            Some(RuntimeValueType::Return) => return Ok(result),
+            _ => {}
+        }
+    }
+    Ok(RuntimeValueServe::Owned(Box::new(StmtExecS {})))
+}
+
+fn eval_range(unwrap: &RangeExpr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let start = unwrap_runtime_value_serve(eval_expr(&unwrap.start, scope)?, scope)?;
+    let end = unwrap_runtime_value_serve(eval_expr(&unwrap.end, scope)?, scope)?;
+    if start.Type() != RuntimeValueType::Numeric || end.Type() != RuntimeValueType::Numeric {
+        return Err(IonError::typing(format!("Range bounds must be numeric | Found: {}..{}", start, end)));
+    }
+    let bound = |value: Box<dyn RuntimeValue>| match fixed::number_of(value.as_ref()) {
+        Some(Number::Int(n)) => Ok(n),
+        _ => Err(IonError::typing(format!("Range bounds must be integers | Found: {}", value))),
+    };
+    Ok(RuntimeValueServe::Owned(Box::new(RangeVal { start: bound(start)?, end: bound(end)? })))
+}
+
+fn eval_for(unwrap: &ForStmt, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let iterable = unwrap_runtime_value_serve(eval_expr(&unwrap.iterable, scope)?, scope)?;
+
+    let items: Box<dyn Iterator<Item = Result<Box<dyn RuntimeValue>, IonError>>> = if let Some(range) = iterable.as_any().downcast_ref::<RangeVal>() {
+        Box::new((range.start..range.end).map(|i| Ok(int_value(i).expect("range bounds are 64 bit integers"))))
+    } else if let Some(arr) = iterable.as_any().downcast_ref::<ArrayLiteralVal>() {
+        let scope = Rc::clone(scope);
+        Box::new(arr.entries.clone().into_iter().map(move |entry| unwrap_runtime_value_serve(entry, &scope)))
+    } else {
+        return Err(IonError::typing(format!("[ for ] can only iterate over an array or a range | Found: {}", iterable)));
+    };
+
+    for item in items {
+        let item = item?;
+        let iter_scope = Rc::new(RefCell::new(Scope::new(Parent::Scope(Rc::clone(scope)))));
+        let flags = infer_flags(item.as_ref());
+        iter_scope.borrow_mut().var_decl(unwrap.item.clone(), RuntimeValueServe::Owned(item), flags)?;
+
+        let result = eval_block(&unwrap.body, &iter_scope)?;
+        match control_signal(&result) {
+            Some(RuntimeValueType::Break) => break,
+            Some(RuntimeValueType::Return) => return Ok(result),
+            _ => {}
+        }
+    }
+    Ok(RuntimeValueServe::Owned(Box::new(StmtExecS {})))
+}
+
+pub fn infer_flags(value: &dyn RuntimeValue) -> Vec<Flags> {
+    if let Some(fixed) = value.as_any().downcast_ref::<FixedVal>() {
+        return match fixed.overflow {
+            Overflow::Checked => vec![Flags::Struct_f(Attr::Fixed(fixed.kind))],
+            policy => vec![Flags::Struct_f(Attr::Fixed(fixed.kind)), Flags::Overflow_f(policy)],
+        };
+    }
+    let attr = match value.Type() {
+        RuntimeValueType::Numeric => Attr::Numeric,
+        RuntimeValueType::String => Attr::String,
+        RuntimeValueType::Boolean => Attr::Bool,
+        RuntimeValueType::ObjectVal => Attr::Object,
+        RuntimeValueType::ArrayVal if value.as_any().is::<ArrayVal>() => Attr::Array,
+        RuntimeValueType::FnStructVal | RuntimeValueType::NativeFn => Attr::Function,
+        _ => {
+            return vec![Flags::Struct_f(Attr::ComplexKind), Flags::Complex_f(Attr::Complex("anonymous".to_string()))];
+        }
+    };
+    vec![Flags::Struct_f(attr)]
+}
+
+fn control_signal(value: &RuntimeValueServe) -> Option<RuntimeValueType> {
+    match value {
+        RuntimeValueServe::Owned(v) => match v.Type() {
+            signal @ (RuntimeValueType::Return | RuntimeValueType::Break | RuntimeValueType::Continue) => Some(signal),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_control_signal(value: &RuntimeValueServe) -> bool {
+    control_signal(value).is_some()
+}
+
+fn misplaced_signal(signal: RuntimeValueType) -> IonError {
+    match signal {
+        RuntimeValueType::Return => IonError::runtime("[ return ] can only be used inside of a function body"),
+        RuntimeValueType::Break => IonError::runtime("[ break ] can only be used inside of a loop"),
+        _ => IonError::runtime("[ continue ] can only be used inside of a loop"),
+    }
 }
 
 //ptr restructure
-fn eval_obj_literal_expr<'a>(unwrap: &ObjectLiteral, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
+fn eval_obj_literal_expr(unwrap: &ObjectLiteral, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
     let mut object = ObjectLiteralVal { properties: HashMap::new() };
     for prop in &unwrap.properties {
-        let ts =
-            match prop.value.kind() {
-                ast::NodeType::Identifier => {
-                    RuntimeValueServe::Ref(prop.value.as_any().downcast_ref::<Identifier>().unwrap().clone())
-                },
-                _ =>  evaluate(prop.value.clone(), scope)
-            };
-
-            object.properties.insert(prop.key.clone(), ts);
+        let ts = eval_stored(&prop.value, scope)?;
+        object.properties.insert(prop.key.clone(), ts);
     }
-    RuntimeValueServe::Owned(Box::new(object))
+    Ok(RuntimeValueServe::Owned(Box::new(object)))
 }
 
-fn eval_obj_expr<'a>(unwrap: &Object, _scope: &'static RefCell<Scope>) -> RuntimeValueServe {
+fn eval_obj_expr(unwrap: &Object, _scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
     let mut object = ObjectVal { properties: HashMap::new() };
     for prop in &unwrap.properties {
         let val = prop.value.clone(); 
 
         object.properties.insert(prop.key.clone(), val);
     }
-    RuntimeValueServe::Owned(Box::new(object))
+    Ok(RuntimeValueServe::Owned(Box::new(object)))
 }
 
-fn eval_fn_struct<'a>(unwrap: &FnStruct, _scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    let mut fn_struct = FuncStructVal{parameters: HashMap::new(), return_type: (&unwrap.ret_type).clone()};
-    for param in &unwrap.params {
-        let val = param.param_type.clone();
-
-        fn_struct.parameters.insert(param.param.clone(), val);
-    }
+fn eval_fn_struct(unwrap: &FnStruct, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let fn_struct = FuncStructVal{
+        parameters: unwrap.params.clone(),
+        return_type: unwrap.ret_type.clone(),
+        body: unwrap.body.clone(),
+        closure: Rc::clone(scope),
+    };
 
-    RuntimeValueServe::Owned(Box::new(fn_struct))
+    Ok(RuntimeValueServe::Owned(Box::new(fn_struct)))
 }
 
-pub fn eval_var_asg<'a>(unwrap: &VarAsg, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    match unwrap.lhs.kind() {
-        NodeType::Identifier => {
-            var_asg_ident(unwrap, scope)
+pub fn eval_var_asg(unwrap: &VarAsg, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    match unwrap.lhs.as_ref() {
+        Expr::Identifier(lhs) => {
+            var_asg_ident(lhs, unwrap, scope)
         },
-        NodeType::MemberExpr => {
-            var_asg_membr_expr(unwrap, scope)
+        Expr::Member(lhs) => {
+            var_asg_membr_expr(lhs, unwrap, scope)
         }
         _ => {
-            panic!("Can't assign value to the same.");
+            Err(IonError::runtime("Can only assign values to an identifier or an object property"))
         }
     }
 }
 
-pub fn var_asg_ident(unwrap: &VarAsg, scope: &'static RefCell<Scope>) -> RuntimeValueServe{
-    let lhs_refined = unwrap.lhs.as_any().downcast_ref::<Identifier>().unwrap(); 
-    
-    // Evaluate once and reuse
-    let evaluated = evaluate(unwrap.rhs.clone(), scope);
-    
-    let ts = match unwrap.rhs.clone().kind() {
-        ast::NodeType::Identifier => {
-            RuntimeValueServe::Ref(unwrap.rhs.clone().as_any().downcast_ref::<Identifier>().unwrap().clone())
-        },
-        _ => evaluated.clone()
-    };
-    
-    let refined_rhs = unwrap_runtime_value_serve(evaluated.clone(), scope);
-
+pub fn var_asg_ident(lhs_refined: &Identifier, unwrap: &VarAsg, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
     let scope_refined = scope.borrow().clone();
-    let _ = scope_refined.resolve(&lhs_refined.symbol);
+    scope_refined.resolve(&lhs_refined.symbol)?;
+
+    let ts = pin_refs(eval_stored(&unwrap.rhs, scope)?, scope, defining_scope(&lhs_refined.symbol, scope).as_ref())?;
+    let refined_rhs = unwrap_runtime_value_serve(ts.clone(), scope)?;
 
-    let f_flag = scope_refined.lookup_flags(lhs_refined.symbol.clone()).iter().find_map(|token_type| {
+    let flags = scope_refined.lookup_flags(lhs_refined.symbol.clone())?;
+    let f_flag = flags.iter().find_map(|token_type| {
         if let crate::lexer::Flags::Struct_f(attr) = token_type {
             Some(attr.clone())
         } else {
             None
         }
-    }).unwrap_or_else(|| panic!("Missing flag <structure> not found in Associated Variable Flags"));
+    }).ok_or_else(|| IonError::runtime("Missing flag <structure> not found in Associated Variable Flags"))?;
 
-    let complex_t: Option<Identifier> = scope_refined.lookup_flags(lhs_refined.symbol.clone()).iter().find_map(|token_type| {
+    let complex_t: Option<Identifier> = flags.iter().find_map(|token_type| {
         if let crate::lexer::Flags::Complex_f(Attr::Complex(attr)) = token_type {
-            Some(Identifier { symbol: attr.clone() })
+            Some(Identifier{symbol: attr.clone(), span: Span::default()})
         } else {
             None
         }
     });
 
-    static_type_check(refined_rhs.clone(), f_flag, complex_t, scope);
+    static_type_check(refined_rhs.clone(), f_flag.clone(), complex_t, scope)?;
+    let ts = match fixed::conform(refined_rhs.as_ref(), &f_flag, &flags)? {
+        Some(fixed) => RuntimeValueServe::Owned(Box::new(fixed)),
+        None => ts,
+    };
 
     scope
         .borrow_mut()
-        .var_assign(lhs_refined.symbol.clone(), ts);
+        .var_assign(lhs_refined.symbol.clone(), ts)?;
 
-    RuntimeValueServe::Owned(Box::new(StmtExecS {}))
+    Ok(RuntimeValueServe::Owned(Box::new(StmtExecS {})))
 }
 
-pub fn var_asg_membr_expr(unwrap: &VarAsg, scope: &'static RefCell<Scope>) -> RuntimeValueServe{
-    let lhs_refined = unwrap.lhs.as_any().downcast_ref::<MemberExpr>().unwrap(); 
-    let obj_as_ident = lhs_refined.obj.as_any().downcast_ref::<Identifier>().unwrap().symbol.clone();
-
-    let new_obj_shell = scope.borrow().clone().lookup(obj_as_ident.clone());
-    let new_obj_weak = match new_obj_shell {
-        RuntimeValueServe::Owned(v) => v,
-        _ => {panic!()}
+pub fn var_asg_membr_expr(lhs_refined: &MemberExpr, unwrap: &VarAsg, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let Expr::Identifier(obj) = lhs_refined.obj.as_ref() else {
+        return Err(IonError::runtime("Can only assign to a property of a variable holding an object"));
     };
-    let mut new_obj= new_obj_weak.clone().as_any().downcast_ref::<ObjectLiteralVal>().unwrap().clone();
-    let prop_mut = new_obj.properties.get_mut(&(lhs_refined.prop.as_any().downcast_ref::<Identifier>().unwrap().symbol.clone())).expect("Property doesn't exist.");
+    let obj_as_ident = obj.symbol.clone();
 
-    match unwrap.rhs.clone().kind() {
-        ast::NodeType::Identifier => {
-            *prop_mut = RuntimeValueServe::Ref(unwrap.rhs.clone().as_any().downcast_ref::<Identifier>().unwrap().clone());
-        },
-       _ =>  {
-           *prop_mut = evaluate(unwrap.rhs.clone(), scope);
-       }
-    }
+    let new_obj_shell = scope.borrow().clone().lookup(obj_as_ident.clone())?;
+    let new_obj_weak = unwrap_runtime_value_serve(new_obj_shell, scope)?;
+    let mut new_obj = new_obj_weak.as_any().downcast_ref::<ObjectLiteralVal>()
+        .ok_or_else(|| IonError::typing(format!("Property assignment can only be executed on an object | Found: {}", new_obj_weak)))?
+        .clone();
+    let prop_name = lhs_refined.prop.symbol.clone();
+    let prop_mut = new_obj.properties.get_mut(&prop_name)
+        .ok_or_else(|| IonError::name(format!("Property '{}' doesn't exist on [ {} ]", prop_name, obj_as_ident)))?;
+
+    *prop_mut = pin_refs(eval_stored(&unwrap.rhs, scope)?, scope, defining_scope(&obj_as_ident, scope).as_ref())?;
 
     let scope_refined = scope.borrow().clone();
-    let _ = scope_refined.resolve(&obj_as_ident);
+    scope_refined.resolve(&obj_as_ident)?;
 
-    let complex_t: Option<Identifier> = scope_refined.lookup_flags(obj_as_ident.clone()).iter().find_map(|token_type| {
+    let complex_t: Option<Identifier> = scope_refined.lookup_flags(obj_as_ident.clone())?.iter().find_map(|token_type| {
         if let crate::lexer::Flags::Complex_f(Attr::Complex(attr)) = token_type {
-            Some(Identifier { symbol: attr.clone() })
+            Some(Identifier{symbol: attr.clone(), span: Span::default()})
         } else {
             None
         }
     });
 
-    static_type_check(Box::new(new_obj.clone()), Attr::ComplexKind, complex_t, scope);
+    static_type_check(Box::new(new_obj.clone()), Attr::ComplexKind, complex_t, scope)?;
 
     scope
         .borrow_mut()
-        .var_assign(obj_as_ident.clone(), RuntimeValueServe::Owned(Box::new(new_obj.clone())));
+        .var_assign(obj_as_ident.clone(), RuntimeValueServe::Owned(Box::new(new_obj.clone())))?;
 
-    RuntimeValueServe::Owned(Box::new(StmtExecS {}))
+    Ok(RuntimeValueServe::Owned(Box::new(StmtExecS {})))
 }
 
-pub fn eval_var_decl<'a>(unwrap: &VarDeclaration, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
+pub fn eval_var_decl(unwrap: &VarDeclaration, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
     if unwrap.identifier == "_"{
-        panic!("Token (_) cannot be used as an identifier.");
+        return Err(IonError::name("Token (_) cannot be used as an identifier."));
     }
     
-    // Evaluate once and reuse
-    let evaluated = evaluate(unwrap.value.clone(), scope);
-    
-    let ts = match unwrap.value.clone().kind() {
-        ast::NodeType::Identifier => {
-            RuntimeValueServe::Ref(unwrap.value.clone().as_any().downcast_ref::<Identifier>().unwrap().clone())
-        },
-        _ => evaluated.clone()
-    };
-    
-    let val_to_store = unwrap_runtime_value_serve(evaluated.clone(), scope);
+    let ts = eval_stored(&unwrap.value, scope)?;
+    let val_to_store = unwrap_runtime_value_serve(ts.clone(), scope)?;
 
-    let f_flag = unwrap.flags.iter().find_map(|token_type| {
+    let flags = declared_flags(&unwrap.identifier, &unwrap.flags, val_to_store.as_ref())?;
+    let f_flag = flags.iter().find_map(|token_type| {
         if let crate::lexer::Flags::Struct_f(attr) = token_type {
             Some(attr.clone())
         } else {
             None
         }
-    }).unwrap_or_else(|| panic!("Missing flag <structure>"));
+    }).expect("declared_flags always yields a <structure>");
 
-    let complex_t: Option<Identifier> = unwrap.flags.iter().find_map(|token_type| {
+    let complex_t: Option<Identifier> = flags.iter().find_map(|token_type| {
         if let crate::lexer::Flags::Complex_f(Attr::Complex(attr)) = token_type {
-            Some(Identifier { symbol: attr.clone() })
+            Some(Identifier{symbol: attr.clone(), span: Span::default()})
         } else {
             None
         }
     });
 
-    static_type_check(val_to_store.clone(), f_flag, complex_t, scope);
+    static_type_check(val_to_store.clone(), f_flag.clone(), complex_t, scope)?;
+    let ts = match fixed::conform(val_to_store.as_ref(), &f_flag, &flags)? {
+        Some(fixed) => RuntimeValueServe::Owned(Box::new(fixed)),
+        None => ts,
+    };
 
     scope
         .borrow_mut()
-        .var_decl(unwrap.identifier.clone(), ts, unwrap.flags.clone());
+        .var_decl(unwrap.identifier.clone(), ts, flags)?;
+
+    Ok(RuntimeValueServe::Owned(Box::new(StmtExecS {})))
+}
 
-    RuntimeValueServe::Owned(Box::new(StmtExecS {}))
+// A declaration's flags, with the `<structure>` it leaves out inferred from its initializer as if it
+// had been written: `<complex: x>` alone implies `<structure: complex>`; nil says nothing about the type.
+pub fn declared_flags(identifier: &str, flags: &[Flags], value: &dyn RuntimeValue) -> Result<Vec<Flags>, IonError> {
+    let mut flags = flags.to_vec();
+    if flags.iter().any(|flag| matches!(flag, Flags::Struct_f(_))) {
+        return Ok(flags);
+    }
+    if flags.iter().any(|flag| matches!(flag, Flags::Complex_f(_))) {
+        flags.push(Flags::Struct_f(Attr::ComplexKind));
+        return Ok(flags);
+    }
+    if value.Type() == RuntimeValueType::Nil {
+        return Err(IonError::runtime(format!("Missing flag <structure> on [ {} ]", identifier))
+            .with_help("the type of a variable declared as nil can't be inferred; add e.g. `<structure: numeric>`"));
+    }
+    flags.extend(infer_flags(value));
+    Ok(flags)
 }
 
 #[derive(Debug)]
@@ -404,6 +564,66 @@ pub enum MinimizedNumeric {
     F64(NumericVal<f64>),
 }
 
+// Boxes a number in the narrowest representation that holds it, as numeric literals are
+pub fn numeric_value(value: f64) -> Box<dyn RuntimeValue> {
+    match minimize_numeric(value) {
+        MinimizedNumeric::I8(v) => Box::new(v),
+        MinimizedNumeric::I16(v) => Box::new(v),
+        MinimizedNumeric::I32(v) => Box::new(v),
+        MinimizedNumeric::I64(v) => Box::new(v),
+        MinimizedNumeric::U8(v) => Box::new(v),
+        MinimizedNumeric::U16(v) => Box::new(v),
+        MinimizedNumeric::U32(v) => Box::new(v),
+        MinimizedNumeric::U64(v) => Box::new(v),
+        MinimizedNumeric::F32(v) => Box::new(v),
+        MinimizedNumeric::F64(v) => Box::new(v),
+    }
+}
+
+pub fn number_value(value: Number) -> Result<Box<dyn RuntimeValue>, IonError> {
+    match value {
+        Number::Int(n) => int_value(n).ok_or_else(|| {
+            IonError::runtime(format!("Integer overflow: {} doesn't fit in 64 bits", n))
+                .with_help("write it with a `.0` to use it as an approximate float")
+        }),
+        Number::Float(n) => Ok(float_value(n)),
+    }
+}
+
+// Floats stay floats, in an f32 when that loses nothing, so `7.0 / 2` is float division
+pub fn float_value(value: f64) -> Box<dyn RuntimeValue> {
+    let as_f32 = value as f32;
+    if as_f32 as f64 == value {
+        Box::new(NumericVal { value: as_f32 })
+    } else {
+        Box::new(NumericVal { value })
+    }
+}
+
+// Boxes an integer exactly, in the narrowest type that holds it; None past 64 bits (i64::MIN..=u64::MAX)
+pub fn int_value(value: i128) -> Option<Box<dyn RuntimeValue>> {
+    let boxed: Box<dyn RuntimeValue> = if value >= 0 {
+        if let Ok(n) = u8::try_from(value) {
+            Box::new(NumericVal { value: n })
+        } else if let Ok(n) = u16::try_from(value) {
+            Box::new(NumericVal { value: n })
+        } else if let Ok(n) = u32::try_from(value) {
+            Box::new(NumericVal { value: n })
+        } else {
+            Box::new(NumericVal { value: u64::try_from(value).ok()? })
+        }
+    } else if let Ok(n) = i8::try_from(value) {
+        Box::new(NumericVal { value: n })
+    } else if let Ok(n) = i16::try_from(value) {
+        Box::new(NumericVal { value: n })
+    } else if let Ok(n) = i32::try_from(value) {
+        Box::new(NumericVal { value: n })
+    } else {
+        Box::new(NumericVal { value: i64::try_from(value).ok()? })
+    };
+    Some(boxed)
+}
+
 pub fn minimize_numeric(value: f64) -> MinimizedNumeric {
     if value.fract() == 0.0 {
         if value >= 0.0 {
@@ -441,31 +661,45 @@ pub fn minimize_numeric(value: f64) -> MinimizedNumeric {
     }
 }
 
-fn eval_identifier<'a>( unwrap: &Identifier, scope: &'static RefCell<Scope> ) -> RuntimeValueServe {
+fn eval_identifier( unwrap: &Identifier, scope: &Rc<RefCell<Scope>> ) -> Result<RuntimeValueServe, IonError> {
     scope.borrow().clone().lookup(unwrap.clone().symbol.to_string())
 }
 
 
-fn eval_program<'a>(astnode: Box<dyn Stmt>, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    let program = astnode.as_any().downcast_ref::<Program>().unwrap();
-
-    if program.body.is_empty() {
-        return RuntimeValueServe::Owned(Box::new(NilVal {}));
+pub fn eval_program(program: &Program, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let result = eval_block(&program.body, scope)?;
+    if let Some(signal) = control_signal(&result) {
+        return Err(misplaced_signal(signal).or_span(program.span));
     }
+    Ok(result)
+}
 
-    for stmt in &program.body[..program.body.len() - 1] {
-        let _ = evaluate(stmt.clone(), scope);
+fn eval_bin_expr(unwrap: &BinExpr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    if unwrap.operator == "&&" || unwrap.operator == "||" {
+        return eval_logical_expr(unwrap, scope);
     }
 
-    evaluate(program.body.last().unwrap().clone(), scope)
-}
-
-fn eval_bin_expr<'a>(unwrap: &BinExpr, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    let lhs = evaluate(unwrap.left.clone(), scope);
-    let rhs = evaluate(unwrap.right.clone(), scope);
+    let lhs = eval_expr(&unwrap.left, scope)?;
+    let rhs = eval_expr(&unwrap.right, scope)?;
 
     match (lhs, rhs) {
         (RuntimeValueServe::Owned(lhs_val), RuntimeValueServe::Owned(rhs_val)) => {
+            match unwrap.operator.as_str() {
+                "==" => return Ok(RuntimeValueServe::Owned(Box::new(BooleanVal { val: values_equal(lhs_val.as_ref(), rhs_val.as_ref(), scope)? }))),
+                "!=" => return Ok(RuntimeValueServe::Owned(Box::new(BooleanVal { val: !values_equal(lhs_val.as_ref(), rhs_val.as_ref(), scope)? }))),
+                "<" | "<=" | ">" | ">=" => {
+                    if lhs_val.Type() != RuntimeValueType::Numeric || rhs_val.Type() != RuntimeValueType::Numeric {
+                        return Err(IonError::typing(format!("Operator [ {} ] can only compare numeric values | Found: {} {} {}", unwrap.operator, lhs_val, unwrap.operator, rhs_val)));
+                    }
+                    return eval_numeric_comparison(
+                        RuntimeValueServe::Owned(lhs_val),
+                        RuntimeValueServe::Owned(rhs_val),
+                        unwrap.operator.as_str(),
+                    );
+                },
+                _ => {}
+            }
+
             if lhs_val.Type() == RuntimeValueType::Numeric
                 && rhs_val.Type() == RuntimeValueType::Numeric
             {
@@ -473,46 +707,148 @@ fn eval_bin_expr<'a>(unwrap: &BinExpr, scope: &'static RefCell<Scope>) -> Runtim
                     RuntimeValueServe::Owned(lhs_val),
                     RuntimeValueServe::Owned(rhs_val),
                     unwrap.operator.as_str(),
-                    scope
                 );
             } else if lhs_val.Type() == RuntimeValueType::String && rhs_val.Type() == RuntimeValueType::String{
-                return RuntimeValueServe::Owned(Box::new(StrLiteral{content: lhs_val.as_any().downcast_ref::<StrLiteral>().unwrap().content.clone() + rhs_val.as_any().downcast_ref::<StrLiteral>().unwrap().content.clone().as_str()}))
+                return Ok(RuntimeValueServe::Owned(Box::new(StrLiteral{content: lhs_val.as_any().downcast_ref::<StrLiteral>().unwrap().content.clone() + rhs_val.as_any().downcast_ref::<StrLiteral>().unwrap().content.clone().as_str()})))
+            }
+            // A variable still holding nil has no value to compute with yet
+            if lhs_val.as_any().is::<NilVal>() || rhs_val.as_any().is::<NilVal>() {
+                return Ok(RuntimeValueServe::Owned(Box::new(NilVal {})));
             }
-            
+            Err(operand_mismatch(&unwrap.operator, lhs_val.as_ref(), rhs_val.as_ref()))
         }
         _ => {
-            panic!("Cannot operate using raw REF during runtime...");
+            Err(IonError::runtime("Cannot operate using raw REF during runtime..."))
+        }
+    }
+}
+
+fn eval_logical_expr(unwrap: &BinExpr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    let lhs = eval_bool_operand(&unwrap.left, &unwrap.operator, scope)?;
+    let val = match unwrap.operator.as_str() {
+        "&&" => lhs && eval_bool_operand(&unwrap.right, &unwrap.operator, scope)?,
+        "||" => lhs || eval_bool_operand(&unwrap.right, &unwrap.operator, scope)?,
+        _ => return Err(IonError::runtime(format!("Invalid operator: {}", unwrap.operator))),
+    };
+    Ok(RuntimeValueServe::Owned(Box::new(BooleanVal { val })))
+}
+
+fn eval_unary_expr(unwrap: &UnaryExpr, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
+    match unwrap.operator.as_str() {
+        "!" => {
+            let val = !eval_bool_operand(&unwrap.operand, &unwrap.operator, scope)?;
+            Ok(RuntimeValueServe::Owned(Box::new(BooleanVal { val })))
+        },
+        _ => Err(IonError::runtime(format!("Invalid operator: {}", unwrap.operator))),
+    }
+}
+
+fn eval_bool_operand(operand: &Expr, op: &str, scope: &Rc<RefCell<Scope>>) -> Result<bool, IonError> {
+    let value = unwrap_runtime_value_serve(eval_expr(operand, scope)?, scope)?;
+    let val = value.as_any().downcast_ref::<BooleanVal>().ok_or_else(|| {
+        IonError::typing(format!("Operator [ {} ] expects operands of type bool | Found: {}", op, value))
+    })?.val;
+    Ok(val)
+}
+
+fn values_equal(lhs: &dyn RuntimeValue, rhs: &dyn RuntimeValue, scope: &Rc<RefCell<Scope>>) -> Result<bool, IonError> {
+    if lhs.Type() == RuntimeValueType::Numeric && rhs.Type() == RuntimeValueType::Numeric {
+        let lhs = RuntimeValueServe::Owned(lhs.clone_box());
+        let rhs = RuntimeValueServe::Owned(rhs.clone_box());
+        if is_float(&lhs) || is_float(&rhs) {
+            return Ok(extract_as_f64(lhs)? == extract_as_f64(rhs)?);
+        }
+        return Ok(extract_as_i128(lhs)? == extract_as_i128(rhs)?);
+    }
+
+    let (l, r) = (lhs.as_any(), rhs.as_any());
+    if let (Some(l), Some(r)) = (l.downcast_ref::<StrLiteral>(), r.downcast_ref::<StrLiteral>()) {
+        return Ok(l.content == r.content);
+    }
+    if let (Some(l), Some(r)) = (l.downcast_ref::<BooleanVal>(), r.downcast_ref::<BooleanVal>()) {
+        return Ok(l.val == r.val);
+    }
+    if l.is::<NilVal>() || r.is::<NilVal>() {
+        return Ok(l.is::<NilVal>() && r.is::<NilVal>());
+    }
+    if let (Some(l), Some(r)) = (l.downcast_ref::<ObjectLiteralVal>(), r.downcast_ref::<ObjectLiteralVal>()) {
+        if l.properties.len() != r.properties.len() {
+            return Ok(false);
         }
+        for (k, lv) in l.properties.iter() {
+            let Some(rv) = r.properties.get(k) else {
+                return Ok(false);
+            };
+            if !values_equal(unwrap_runtime_value_serve(lv.clone(), scope)?.as_ref(), unwrap_runtime_value_serve(rv.clone(), scope)?.as_ref(), scope)? {
+                return Ok(false);
+            }
+        }
+        return Ok(true);
+    }
+    if let (Some(l), Some(r)) = (l.downcast_ref::<ArrayLiteralVal>(), r.downcast_ref::<ArrayLiteralVal>()) {
+        if l.entries.len() != r.entries.len() {
+            return Ok(false);
+        }
+        for (lv, rv) in l.entries.iter().zip(r.entries.iter()) {
+            if !values_equal(unwrap_runtime_value_serve(lv.clone(), scope)?.as_ref(), unwrap_runtime_value_serve(rv.clone(), scope)?.as_ref(), scope)? {
+                return Ok(false);
+            }
+        }
+        return Ok(true);
     }
+    Ok(false)
+}
+
+fn eval_numeric_comparison(lhs_val: RuntimeValueServe, rhs_val: RuntimeValueServe, op: &str) -> Result<RuntimeValueServe, IonError> {
+    let ordering = if is_float(&lhs_val) || is_float(&rhs_val) {
+        extract_as_f64(lhs_val)?.partial_cmp(&extract_as_f64(rhs_val)?)
+    } else {
+        Some(extract_as_i128(lhs_val)?.cmp(&extract_as_i128(rhs_val)?))
+    };
 
-    RuntimeValueServe::Owned(Box::new(NilVal {}))
+    let val = match ordering {
+        Some(ordering) => match op {
+            "<" => ordering.is_lt(),
+            "<=" => ordering.is_le(),
+            ">" => ordering.is_gt(),
+            ">=" => ordering.is_ge(),
+            _ => return Err(IonError::runtime(format!("Invalid operator: {}", op))),
+        },
+        None => false,
+    };
+    Ok(RuntimeValueServe::Owned(Box::new(BooleanVal { val })))
 }
 
-fn extract_as_i64(val: RuntimeValueServe) -> i64 {
+// Every integer width, u64 and i64 included, exactly
+fn extract_as_i128(val: RuntimeValueServe) -> Result<i128, IonError> {
     match val {
         RuntimeValueServe::Owned(v) => {
             let any = v.as_any();
-            extract_numeric!(any, i64, [u8, u16, u32, u64, i8, i16, i32, i64]);
-            panic!("Expected integer-compatible value for op");
+            if let Some(Number::Int(n)) = any.downcast_ref::<FixedVal>().map(|fixed| fixed.value) {
+                return Ok(n);
+            }
+            extract_numeric!(any, i128, [u8, u16, u32, u64, i8, i16, i32, i64]);
+            Err(IonError::typing(format!("Expected integer-compatible value for op | Found: {}", v)))
         },
         RuntimeValueServe::Ref(v) => {
-            let any = v.as_any();
-            extract_numeric!(any, i64, [u8, u16, u32, u64, i8, i16, i32, i64]);
-            panic!("Expected integer-compatible value for op");
+            Err(IonError::typing(format!("Expected integer-compatible value for op | Found: {}", v.symbol)))
         }
     }
 }
-fn extract_as_f64(val: RuntimeValueServe) -> f64 {
+pub fn extract_as_f64(val: RuntimeValueServe) -> Result<f64, IonError> {
     match val {
         RuntimeValueServe::Owned(v) => {
             let any = v.as_any();
+            match any.downcast_ref::<FixedVal>().map(|fixed| fixed.value) {
+                Some(Number::Int(n)) => return Ok(n as f64),
+                Some(Number::Float(n)) => return Ok(n),
+                None => {},
+            }
             extract_numeric!(any, f64, [f64, f32, u8, u16, u32, u64, i8, i16, i32, i64]);
-            panic!("Expected numeric value for f64 cast");
+            Err(IonError::typing(format!("Expected numeric value for f64 cast | Found: {}", v)))
         },
         RuntimeValueServe::Ref(v) => {
-            let any = v.as_any();
-            extract_numeric!(any, f64, [f64, f32, u8, u16, u32, u64, i8, i16, i32, i64]);
-            panic!("Expected numeric value for f64 cast");
+            Err(IonError::typing(format!("Expected numeric value for f64 cast | Found: {}", v.symbol)))
         }
 
     }
@@ -521,100 +857,140 @@ fn is_float(val: &RuntimeValueServe) -> bool {
     match val {
         RuntimeValueServe::Owned(v) => {
             let any = v.as_any();
-            any.is::<NumericVal<f64>>() || any.is::<NumericVal<f32>>()
+            any.is::<NumericVal<f64>>() || any.is::<NumericVal<f32>>() || any.downcast_ref::<FixedVal>().is_some_and(|fixed| fixed.kind.is_float())
         }
         _ => false,
     }
 }
 
-pub fn eval_numeric_bin_expr<'a>(
+pub fn eval_numeric_bin_expr(
     lhs_val: RuntimeValueServe,
     rhs_val: RuntimeValueServe,
     op: &str,
-    scope: &'static RefCell<Scope>,
-) -> RuntimeValueServe {
-
-    let should_use_float = is_float(&lhs_val) || is_float(&rhs_val);
-
-    let result = if should_use_float {
-        let lhs = extract_as_f64(lhs_val);
-        let rhs = extract_as_f64(rhs_val);
-        match op {
-            "+" => lhs + rhs,
-            "-" => lhs - rhs,
-            "*" => lhs * rhs,
-            "/" => {
-                if rhs == 0.0 {
-                    panic!("Division by zero");
-                }
-                lhs / rhs
-            },
-            "%" => {
-                if rhs == 0.0 {
-                    panic!("Modulo by zero");
-                }
-                lhs % rhs
-            },
-            _ => panic!("Invalid operator: {}", op),
-        }
-    } else {
-        let lhs = extract_as_i64(lhs_val) as f64;
-        let rhs = extract_as_i64(rhs_val) as f64;
-        match op {
-            "+" => lhs + rhs,
-            "-" => lhs - rhs,
-            "*" => lhs * rhs,
-            "/" => {
-                if rhs == 0.0 {
-                    panic!("Division by zero");
-                }
-                lhs / rhs
-            },
-            "%" => {
-                if rhs == 0.0 {
-                    panic!("Modulo by zero");
-                }
-                lhs % rhs
-            },
-            _ => panic!("Invalid operator: {}", op),
+) -> Result<RuntimeValueServe, IonError> {
+    if let (RuntimeValueServe::Owned(lhs), RuntimeValueServe::Owned(rhs)) = (&lhs_val, &rhs_val) {
+        if let Some(result) = fixed::eval_fixed_bin_expr(lhs.as_ref(), rhs.as_ref(), op) {
+            return result;
         }
+    }
+
+    // Only an actual f32 / f64 operand makes this float arithmetic
+    if !is_float(&lhs_val) && !is_float(&rhs_val) {
+        return eval_integer_bin_expr(extract_as_i128(lhs_val)?, extract_as_i128(rhs_val)?, op);
+    }
+
+    let lhs = extract_as_f64(lhs_val)?;
+    let rhs = extract_as_f64(rhs_val)?;
+    let result = match op {
+        "+" => lhs + rhs,
+        "-" => lhs - rhs,
+        "*" => lhs * rhs,
+        "/" => {
+            if rhs == 0.0 {
+                return Err(IonError::runtime("Division by zero"));
+            }
+            lhs / rhs
+        },
+        "%" => {
+            if rhs == 0.0 {
+                return Err(IonError::runtime("Modulo by zero"));
+            }
+            lhs % rhs
+        },
+        _ => return Err(IonError::runtime(format!("Invalid operator: {}", op))),
     };
 
-    let temp_expr = Box::new(NumericLiteral { value: result });
-    evaluate(temp_expr, scope)
+    Ok(RuntimeValueServe::Owned(float_value(result)))
+}
+
+// Integers stay exact: `/` truncates toward zero and `%` takes the sign of the dividend, as in Rust,
+// and a result outside i64::MIN..=u64::MAX is an error rather than a rounded float
+fn eval_integer_bin_expr(lhs: i128, rhs: i128, op: &str) -> Result<RuntimeValueServe, IonError> {
+    let result = match op {
+        "+" => lhs.checked_add(rhs),
+        "-" => lhs.checked_sub(rhs),
+        "*" => lhs.checked_mul(rhs),
+        "/" => {
+            if rhs == 0 {
+                return Err(IonError::runtime("Division by zero"));
+            }
+            lhs.checked_div(rhs)
+        },
+        "%" => {
+            if rhs == 0 {
+                return Err(IonError::runtime("Modulo by zero"));
+            }
+            lhs.checked_rem(rhs)
+        },
+        _ => return Err(IonError::runtime(format!("Invalid operator: {}", op))),
+    };
+    let value = result.and_then(int_value).ok_or_else(|| {
+        IonError::runtime(format!("Integer overflow: {} {} {} doesn't fit in 64 bits", lhs, op, rhs))
+            .with_help("make an operand a float (`2.0`) to compute it approximately")
+    })?;
+    Ok(RuntimeValueServe::Owned(value))
 }
 
 
-pub fn static_type_check<'a>(value: Box<dyn RuntimeValue>, type_ideal: Attr, complex: Option<Identifier>, scope: &'static RefCell<Scope>) {
+pub fn static_type_check(value: Box<dyn RuntimeValue>, type_ideal: Attr, complex: Option<Identifier>, scope: &Rc<RefCell<Scope>>) -> Result<(), IonError> {
     match type_ideal {
         Attr::Numeric => {
             if is_numeric_val(&value) || value.as_any().downcast_ref::<NilVal>().is_some() {} 
-            else {panic!("Incorrect Type Assignement");}
+            else {return Err(type_mismatch(&type_ideal, value.as_ref()));}
         },
         Attr::String => {
             if value.as_any().downcast_ref::<StrLiteral>().is_some() || value.as_any().downcast_ref::<NilVal>().is_some() {} 
-            else {panic!("Incorrect Type Assignement");}
+            else {return Err(type_mismatch(&type_ideal, value.as_ref()));}
         },
         Attr::Bool => {
             if value.as_any().downcast_ref::<BooleanVal>().is_some() || value.as_any().downcast_ref::<NilVal>().is_some(){}
-            else {panic!("Incorrect Type Assignement");}
+            else {return Err(type_mismatch(&type_ideal, value.as_ref()));}
         },
         Attr::Object => {
             if value.as_any().downcast_ref::<ObjectVal>().is_some() || value.as_any().downcast_ref::<NilVal>().is_some(){}
-            else {panic!("Incorrect Type Assignement");}
+            else {return Err(type_mismatch(&type_ideal, value.as_ref()));}
         },
         Attr::Array => {
             if value.as_any().downcast_ref::<ArrayVal>().is_some() || value.as_any().downcast_ref::<NilVal>().is_some(){}
-            else {panic!("Incorrect Type Assignement");}
+            else {return Err(type_mismatch(&type_ideal, value.as_ref()));}
+        },
+        Attr::Function => {
+            if value.as_any().downcast_ref::<FuncStructVal>().is_some() || value.as_any().downcast_ref::<NativeFnValue>().is_some() || value.as_any().downcast_ref::<NilVal>().is_some(){}
+            else {return Err(type_mismatch(&type_ideal, value.as_ref()));}
+        },
+        Attr::Fixed(kind) => {
+            if value.as_any().downcast_ref::<NilVal>().is_none() {
+                fixed::fixed_value(value.as_ref(), kind, Default::default())?;
+            }
         },
         Attr::ComplexKind => {
-            let unwrap = complex.unwrap_or_else(|| panic!("Complex Struct defined without complex flag specification"));
-            complex_static_type_check(unwrap, value, scope);
+            let unwrap = complex.ok_or_else(|| IonError::typing("Complex Struct defined without complex flag specification"))?;
+            complex_static_type_check(unwrap, value, scope)?;
         }
-        _ => {
-            panic!("Can't use as type");
+        Attr::Complex(ref name) => {
+            let err = IonError::typing(format!("Unknown <structure: {}>", name));
+            return Err(match suggest(name, ATTR_NAMES) {
+                Some(attr) => err.with_help(format!("did you mean `<structure: {}>`?", attr)),
+                None => err.with_help(format!("use `<structure: complex> <complex: {}>`", name)),
+            });
         }
     }
+    Ok(())
+}
+
+fn type_mismatch(expected: &Attr, value: &dyn RuntimeValue) -> IonError {
+    IonError::typing(format!("Incorrect Type Assignement | Expected: {:?} | Found: {}", expected, value))
+}
+
+pub fn operand_mismatch(operator: &str, left: &dyn fmt::Display, right: &dyn fmt::Display) -> IonError {
+    IonError::typing(format!("Operator [ {} ] expects two numeric or two string operands | Found: {} {} {}", operator, left, operator, right))
+}
+
+fn attr_type_check(value: Box<dyn RuntimeValue>, attr: &Attr, scope: &Rc<RefCell<Scope>>) -> Result<(), IonError> {
+    match attr {
+        Attr::Complex(cmplx) => static_type_check(value, Attr::ComplexKind, Some(Identifier{symbol: cmplx.clone(), span: Span::default()}), scope),
+        _ => static_type_check(value, attr.clone(), None, scope),
+    }
 }
 
 //openfull
@@ -625,7 +1001,7 @@ macro_rules! is_numeric_val {
 }
 
 fn is_numeric_val(value: &Box<dyn RuntimeValue>) -> bool {
-    is_numeric_val!(
+    value.as_any().is::<FixedVal>() || is_numeric_val!(
         value,
         i8, i16, i32, i64,
         u8, u16, u32, u64,
@@ -633,68 +1009,126 @@ fn is_numeric_val(value: &Box<dyn RuntimeValue>) -> bool {
     )
 }
 
-fn complex_static_type_check<'a>(ideal: Identifier, value: Box<dyn RuntimeValue>, scope: &'static RefCell<Scope>) {
+fn complex_static_type_check(ideal: Identifier, value: Box<dyn RuntimeValue>, scope: &Rc<RefCell<Scope>>) -> Result<(), IonError> {
     if ideal.symbol == "anonymous" {
-        return;
+        return Ok(());
     }
-    let lookup = eval_identifier(&ideal, scope);
+    let lookup = eval_identifier(&ideal, scope).map_err(|err| match suggest(&ideal.symbol, ATTR_NAMES) {
+        Some(attr) => err.with_help(format!("did you mean `{}`?", attr)),
+        None => err,
+    })?;
     if let RuntimeValueServe::Owned(lookup_unwrap) = lookup{
        if lookup_unwrap.as_any().downcast_ref::<ObjectVal>().is_some(){
             let lookup_refined = lookup_unwrap.as_any().downcast_ref::<ObjectVal>().unwrap();
-            let v_refined = value.as_any().downcast_ref::<ObjectLiteralVal>().unwrap_or_else(||{
-                        panic!("{}", format!("Expected an object of type: {} | Found: {}", lookup_refined, value));
-                });
+            let v_refined = value.as_any().downcast_ref::<ObjectLiteralVal>().ok_or_else(||{
+                        IonError::typing(format!("Expected an object of type: {} | Found: {}", lookup_refined, value))
+                })?;
             for prop in lookup_refined.properties.iter() {
                 let k = prop.0.as_str();
                 let v = prop.1;
                 if v_refined.properties.get(k).is_some(){}
-                else {panic!("Incorrect Type Assignement");}
+                else {return Err(IonError::typing(format!("Missing field [ {} ] on an object of type: {}", k, ideal.symbol)));}
 
                 if let Attr::Complex(ref cmplx) = v.clone() {
-                    static_type_check(unwrap_runtime_value_serve( v_refined.properties.get(k).unwrap_or_else(|| {
-                        panic!("{}", format!("Property {:?} doesn't exist on expression {:?}", k, v_refined));
-                    }).clone(), scope ) , Attr::ComplexKind, Some(Identifier{symbol: cmplx.clone()}), scope);
+                    static_type_check(unwrap_runtime_value_serve( v_refined.properties.get(k).ok_or_else(|| {
+                        IonError::name(format!("Property {:?} doesn't exist on expression {:?}", k, v_refined))
+                    })?.clone(), scope )? , Attr::ComplexKind, Some(Identifier{symbol: cmplx.clone(), span: Span::default()}), scope)?;
                 }else{
-                    static_type_check(unwrap_runtime_value_serve(v_refined.properties.get(k).unwrap_or_else(||{
-                        panic!("{}", format!("Property {k} doesn't exist on expression {v_refined}"));
-                    }).clone(), scope), v.clone(), None, scope);
+                    static_type_check(unwrap_runtime_value_serve(v_refined.properties.get(k).ok_or_else(||{
+                        IonError::name(format!("Property {k} doesn't exist on expression {v_refined}"))
+                    })?.clone(), scope)?, v.clone(), None, scope)?;
                 }
 
                 
             }
             for prop in v_refined.properties.iter() {
                 if !lookup_refined.properties.get(prop.0).is_some() {
-                    panic!("Extra Fields")
+                    return Err(IonError::typing(format!("Extra field [ {} ] on an object of type: {}", prop.0, ideal.symbol)));
                 }
             }
        }
        if lookup_unwrap.as_any().downcast_ref::<ArrayVal>().is_some(){
             let lookup_refined = lookup_unwrap.as_any().downcast_ref::<ArrayVal>().unwrap();
-            let v_refined = value.as_any().downcast_ref::<ArrayLiteralVal>().unwrap();
+            let v_refined = value.as_any().downcast_ref::<ArrayLiteralVal>().ok_or_else(|| {
+                IonError::typing(format!("Expected an array of type: {} | Found: {}", ideal.symbol, value))
+            })?;
             if v_refined.entries.len() != lookup_refined.length {
-                panic!("The size of an array must be strictly equal to that of its complex.");
+                return Err(IonError::typing("The size of an array must be strictly equal to that of its complex."));
             }
             for entry in v_refined.entries.clone() {
-                static_type_check(unwrap_runtime_value_serve(entry.clone(), scope), lookup_refined.attr.clone(), lookup_refined.complex.clone(), scope);
+                static_type_check(unwrap_runtime_value_serve(entry.clone(), scope)?, lookup_refined.attr.clone(), lookup_refined.complex.clone(), scope)?;
             }
        }
     }else{
-        panic!("I honestly dont get paid enough for this");
+        return Err(IonError::typing(format!("Complex attr [ {} ] must refer to an object or array struct", ideal.symbol)));
     }
+    Ok(())
 }
 
-pub fn unwrap_runtime_value_serve<'a>(value: RuntimeValueServe, scope: &'static RefCell<Scope>) -> Box<dyn RuntimeValue> {
+pub fn unwrap_runtime_value_serve(value: RuntimeValueServe, scope: &Rc<RefCell<Scope>>) -> Result<Box<dyn RuntimeValue>, IonError> {
     match value {
-        RuntimeValueServe::Owned(val) => val,
+        RuntimeValueServe::Owned(val) => Ok(val),
         RuntimeValueServe::Ref(val) => {
-                match scope.borrow().clone().lookup(val.clone().symbol.to_string()){
-                    RuntimeValueServe::Owned(v) => v,
+                match scope.borrow().clone().lookup(val.clone().symbol.to_string())? {
+                    RuntimeValueServe::Owned(v) => Ok(v),
                     _ => {
-                        panic!("Internal Interpreter Error; Unable to handle raw REF during runtime...");
+                        Err(IonError::runtime("Internal Interpreter Error; Unable to handle raw REF during runtime..."))
                     }
                 }
         },
     }
 }
 
+// The scope `name` is declared in, as seen from `scope`
+fn defining_scope(name: &str, scope: &Rc<RefCell<Scope>>) -> Option<Rc<RefCell<Scope>>> {
+    if scope.borrow().variables.contains_key(name) {
+        return Some(Rc::clone(scope));
+    }
+    match &scope.borrow().parent {
+        Parent::Scope(parent) => defining_scope(name, parent),
+        Parent::Nil => None,
+    }
+}
+
+fn encloses(outer: &Rc<RefCell<Scope>>, scope: &Rc<RefCell<Scope>>) -> bool {
+    if Rc::ptr_eq(outer, scope) {
+        return true;
+    }
+    match &scope.borrow().parent {
+        Parent::Scope(parent) => encloses(outer, parent),
+        Parent::Nil => false,
+    }
+}
+
+// A value evaluated in `scope` as it can be kept in `target`: names (nested ones included) that
+// `target` sees stay references, the others (loop variables, locals of a block or function that
+// is about to end) are replaced by their value. No `target` resolves every name.
+pub fn pin_refs(value: RuntimeValueServe, scope: &Rc<RefCell<Scope>>, target: Option<&Rc<RefCell<Scope>>>) -> Result<RuntimeValueServe, IonError> {
+    if let RuntimeValueServe::Ref(ident) = &value {
+        let visible = match (target, defining_scope(&ident.symbol, scope)) {
+            (Some(target), Some(declared)) => encloses(&declared, target),
+            _ => false,
+        };
+        if visible {
+            return Ok(value);
+        }
+    }
+
+    let value = unwrap_runtime_value_serve(value, scope)?;
+    if let Some(obj) = value.as_any().downcast_ref::<ObjectLiteralVal>() {
+        let mut properties = HashMap::new();
+        for (key, prop) in &obj.properties {
+            properties.insert(key.clone(), pin_refs(prop.clone(), scope, target)?);
+        }
+        return Ok(RuntimeValueServe::Owned(Box::new(ObjectLiteralVal { properties })));
+    }
+    if let Some(arr) = value.as_any().downcast_ref::<ArrayLiteralVal>() {
+        let entries = arr.entries.iter()
+            .map(|entry| pin_refs(entry.clone(), scope, target))
+            .collect::<Result<_, _>>()?;
+        return Ok(RuntimeValueServe::Owned(Box::new(ArrayLiteralVal { entries })));
+    }
+    Ok(RuntimeValueServe::Owned(value))
+}
+
 //openfull
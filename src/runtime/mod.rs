@@ -3,5 +3,5 @@ pub mod interpreter;
 pub mod scopes;
 pub mod values_impls;
 pub mod complex_values_impls;
-
-pub const PRINT_:bool = true;
+pub mod native;
+pub mod fixed;
@@ -0,0 +1,163 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::IonError;
+use crate::ast::Number;
+use crate::fixed::number_of;
+use crate::interpreter::{extract_as_f64, float_value, int_value, unwrap_runtime_value_serve, RuntimeValueServe};
+use crate::scopes::Scope;
+use crate::values::{BooleanVal, NilVal, RuntimeValue, RuntimeValueType, StrLiteral};
+
+// Conversions used by `#[ion_native]` to move values between scripts and typed Rust functions.
+
+pub trait FromValue: Sized {
+    // Shown in the type error when an argument doesn't convert
+    const EXPECTED: &'static str;
+
+    fn from_value(value: &dyn RuntimeValue) -> Option<Self>;
+}
+
+pub trait IntoValue {
+    fn into_value(self) -> Box<dyn RuntimeValue>;
+}
+
+// What a native may return: a plain value, or a `Result` whose error is raised in the script
+pub trait NativeReturn {
+    fn into_result(self) -> Result<RuntimeValueServe, IonError>;
+}
+
+impl<T: IntoValue> NativeReturn for T {
+    fn into_result(self) -> Result<RuntimeValueServe, IonError> {
+        Ok(RuntimeValueServe::Owned(self.into_value()))
+    }
+}
+
+impl<T: IntoValue> NativeReturn for Result<T, IonError> {
+    fn into_result(self) -> Result<RuntimeValueServe, IonError> {
+        self.map(|value| RuntimeValueServe::Owned(value.into_value()))
+    }
+}
+
+pub fn from_arg<T: FromValue>(arg: RuntimeValueServe, scope: &Rc<RefCell<Scope>>, func: &str, param: &str) -> Result<T, IonError> {
+    let value = unwrap_runtime_value_serve(arg, scope)?;
+    T::from_value(value.as_ref()).ok_or_else(|| {
+        IonError::typing(format!("Argument [ {} ] of [ {} ] | Expected: {} | Found: {}", param, func, T::EXPECTED, value))
+    })
+}
+
+// Literals are stored in the narrowest type that fits (`3` is a u8), so any width is accepted
+fn as_f64(value: &dyn RuntimeValue) -> Option<f64> {
+    if value.Type() != RuntimeValueType::Numeric {
+        return None;
+    }
+    extract_as_f64(RuntimeValueServe::Owned(value.clone_box())).ok()
+}
+
+impl FromValue for f64 {
+    const EXPECTED: &'static str = "numeric";
+
+    fn from_value(value: &dyn RuntimeValue) -> Option<f64> {
+        as_f64(value)
+    }
+}
+
+impl FromValue for f32 {
+    const EXPECTED: &'static str = "numeric";
+
+    fn from_value(value: &dyn RuntimeValue) -> Option<f32> {
+        as_f64(value).map(|n| n as f32)
+    }
+}
+
+macro_rules! integer_conversions {
+    ($($ty:ty),*) => {
+        $(
+            impl FromValue for $ty {
+                const EXPECTED: &'static str = concat!("integer (", stringify!($ty), ")");
+
+                fn from_value(value: &dyn RuntimeValue) -> Option<$ty> {
+                    match number_of(value)? {
+                        Number::Int(n) => <$ty>::try_from(n).ok(),
+                        Number::Float(n) if n.fract() == 0.0 && n >= <$ty>::MIN as f64 && n <= <$ty>::MAX as f64 => Some(n as $ty),
+                        Number::Float(_) => None,
+                    }
+                }
+            }
+
+            impl IntoValue for $ty {
+                fn into_value(self) -> Box<dyn RuntimeValue> {
+                    int_value(self as i128).expect("64 bit integers always fit")
+                }
+            }
+        )*
+    };
+}
+
+integer_conversions!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl IntoValue for f64 {
+    fn into_value(self) -> Box<dyn RuntimeValue> {
+        float_value(self)
+    }
+}
+
+impl IntoValue for f32 {
+    fn into_value(self) -> Box<dyn RuntimeValue> {
+        float_value(self as f64)
+    }
+}
+
+impl FromValue for bool {
+    const EXPECTED: &'static str = "bool";
+
+    fn from_value(value: &dyn RuntimeValue) -> Option<bool> {
+        value.as_any().downcast_ref::<BooleanVal>().map(|b| b.val)
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Box<dyn RuntimeValue> {
+        Box::new(BooleanVal{val: self})
+    }
+}
+
+impl FromValue for String {
+    const EXPECTED: &'static str = "string";
+
+    fn from_value(value: &dyn RuntimeValue) -> Option<String> {
+        value.as_any().downcast_ref::<StrLiteral>().map(|s| s.content.clone())
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Box<dyn RuntimeValue> {
+        Box::new(StrLiteral{content: self})
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Box<dyn RuntimeValue> {
+        Box::new(StrLiteral{content: self.to_string()})
+    }
+}
+
+// Untyped values pass through as is
+impl FromValue for Box<dyn RuntimeValue> {
+    const EXPECTED: &'static str = "any value";
+
+    fn from_value(value: &dyn RuntimeValue) -> Option<Box<dyn RuntimeValue>> {
+        Some(value.clone_box())
+    }
+}
+
+impl IntoValue for Box<dyn RuntimeValue> {
+    fn into_value(self) -> Box<dyn RuntimeValue> {
+        self
+    }
+}
+
+impl IntoValue for () {
+    fn into_value(self) -> Box<dyn RuntimeValue> {
+        Box::new(NilVal{})
+    }
+}
@@ -1,11 +1,15 @@
+use crate::diagnostic::suggest;
+use crate::error::IonError;
 use crate::interpreter::{unwrap_runtime_value_serve, RuntimeValueServe};
-use crate::lexer::Flags;
+use crate::lexer::{Attr, Flags};
 use crate::values::{NativeFnValue, NilVal};
+use ion_macros::ion_native;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::io::{self, Write};
 
-use super::values::StrLiteral;
+use super::values::{ArrayLiteralVal, StrLiteral};
 
 #[derive(Clone)]
 pub struct VariableEntry {
@@ -16,7 +20,7 @@ pub struct VariableEntry {
 
 #[derive(Clone)]
 pub enum Parent{
-    Scope(Box<Scope>),
+    Scope(Rc<RefCell<Scope>>),
     Nil,
 }
 
@@ -33,80 +37,80 @@ impl Scope{
            variables: HashMap::new(),
        }
     }
-    pub fn var_decl(&mut self, varname: String, value: RuntimeValueServe, flags: Vec<Flags>) ->  RuntimeValueServe {
-        if self.variables.get(&varname).is_some() {panic!("{}", format!("variable already defined [{:?}]", varname));}
-        match value.clone() {
-            RuntimeValueServe::Owned(_) => {
-                self.variables.insert(varname, VariableEntry{value: value.clone(), flags, locked: false});
-            },
-            RuntimeValueServe::Ref(_) => {
-                self.variables.insert(varname, VariableEntry{value: value.clone(), flags, locked: false});
-            }
+    pub fn var_decl(&mut self, varname: String, value: RuntimeValueServe, flags: Vec<Flags>) -> Result<RuntimeValueServe, IonError> {
+        if self.variables.contains_key(&varname) {
+            return Err(IonError::name(format!("Variable [{}] is already defined in the current scope", varname)));
+        }
+        self.variables.insert(varname, VariableEntry{value: value.clone(), flags, locked: false});
+        Ok(value)
+    }
+
+    pub fn resolve(&self, varname: &String) -> Result<(), IonError> {
+        match self.entry(varname) {
+            Some(_) => Ok(()),
+            None => Err(self.undefined(varname)),
         }
-        return value;
     }
 
-    pub fn resolve(&self, varname: &String) -> &Scope {
-        if self.variables.recursive_lookup(varname).is_some() {
-            return self; 
+    pub fn var_assign(&mut self, varname: String, value: RuntimeValueServe) -> Result<RuntimeValueServe, IonError> {
+        if self.lookup_flags(varname.clone())?.contains(&Flags::Const_f) {
+            return Err(IonError::runtime(format!("Cannot reassign variable [{}] marked with flag: <const>", varname)));
         }
-        match &self.parent {
-            Parent::Nil => {
-                panic!("Variable [{}] doesn't exist in the current scope!", varname.clone());
+
+        match self.variables.get_mut(&varname) {
+            Some(k) => {
+                while k.locked{};
+                k.locked = true;
+                k.value = value.clone();
+                k.locked = false;
+                Ok(value)
             },
-            Parent::Scope(s) => {
-                return s.resolve(varname);
+            None => match &self.parent {
+                Parent::Scope(s) => s.borrow_mut().var_assign(varname, value),
+                Parent::Nil => Err(self.undefined(&varname)),
             }
         }
     }
-    pub fn resolve_mut(&mut self, varname: &String) -> &mut Scope {
-        if self.variables.recursive_lookup(varname).is_some() {
-            return self;
-        }
 
-        match &mut self.parent {
-            Parent::Scope(s) => s.resolve_mut(varname),
-            Parent::Nil => panic!("Variable doesn't exist in the current scope"),
+    pub fn lookup(&self, varname: String) -> Result<RuntimeValueServe, IonError> {
+        match self.entry(&varname) {
+            Some(entry) => Ok(entry.value),
+            None => Err(self.undefined(&varname)),
         }
     }
 
-    pub fn var_assign(&mut self, varname: String, value: RuntimeValueServe) -> RuntimeValueServe {
-        if self.lookup_flags(varname.clone()).contains(&Flags::Const_f) {panic!("Cannot reassign variable marked with flag: <const>")}
-
-        let env = self.resolve_mut(&varname);
-        let k = env.variables.get_mut(&varname).unwrap();
-        while k.locked{};
-        k.locked = true;
-        k.value = value.clone();
-        k.locked = false;
-        value
+    pub fn lookup_flags(&self, varname: String) -> Result<Vec<Flags>, IonError> {
+        match self.entry(&varname) {
+            Some(entry) => Ok(entry.flags),
+            None => Err(self.undefined(&varname)),
+        }
     }
 
-    pub fn lookup(&self, varname: String) -> RuntimeValueServe {
-        let env = self.resolve(&varname);
-        env.variables.get(&varname).unwrap().value.clone()
+    fn entry(&self, varname: &String) -> Option<VariableEntry> {
+        match self.variables.get(varname) {
+            Some(entry) => Some(entry.clone()),
+            None => match &self.parent {
+                Parent::Scope(s) => s.borrow().entry(varname),
+                Parent::Nil => None,
+            }
+        }
     }
 
-    pub fn lookup_flags(&self, varname: String) -> &Vec<Flags> {
-        let env = self.resolve(&varname);
-        &env.variables.get(&varname).unwrap().flags
+    // Every name reachable from this scope, innermost first
+    fn visible_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.variables.keys().cloned().collect();
+        if let Parent::Scope(s) = &self.parent {
+            names.extend(s.borrow().visible_names());
+        }
+        names
     }
-}
 
-trait RecursiveLookup {
-    fn recursive_lookup(&self, key: &String) -> Option<RuntimeValueServe>;
-}
-
-impl RecursiveLookup for HashMap<String, VariableEntry> {
-    fn recursive_lookup(&self, key: &String) -> Option< RuntimeValueServe > {
-        let entry = self.get(key).unwrap();
-        match entry.value.clone() {
-            RuntimeValueServe::Owned(v) => {
-                Some(RuntimeValueServe::Owned(v))
-            },
-            RuntimeValueServe::Ref(s) => {
-                self.recursive_lookup(&String::from(s.symbol))
-            }
+    fn undefined(&self, varname: &str) -> IonError {
+        let err = IonError::name(format!("Variable [{}] doesn't exist in the current scope!", varname));
+        let names = self.visible_names();
+        match suggest(varname, names.iter().map(|name| name.as_str())) {
+            Some(name) => err.with_help(format!("a variable with a similar name exists: `{}`", name)),
+            None => err,
         }
     }
 }
@@ -119,26 +123,28 @@ impl Default for RuntimeValueServe {
 
 //native
 
-pub fn init<'a>() -> Scope {
+pub fn init(args: Vec<String>) -> Scope {
    let mut env = Scope::new(Parent::Nil);
-   env.var_decl("log".to_string(), RuntimeValueServe::Owned(  Box::new(NativeFnValue{call: Box::new(log_fn as fn(_, _) -> _)}) ), vec![Flags::Const_f]);
-   env.var_decl("get".to_string(), RuntimeValueServe::Owned(  Box::new(NativeFnValue{call: Box::new(get_fn as fn(_, _) -> _)}) ), vec![Flags::Const_f]);
+   let args = ArrayLiteralVal{entries: args.into_iter().map(|arg| RuntimeValueServe::Owned(Box::new(StrLiteral{content: arg}))).collect()};
+   env.var_decl("args".to_string(), RuntimeValueServe::Owned(Box::new(args)), vec![Flags::Const_f, Flags::Struct_f(Attr::ComplexKind), Flags::Complex_f(Attr::Complex("anonymous".to_string()))]).unwrap();
+   env.var_decl("log".to_string(), RuntimeValueServe::Owned(  Box::new(NativeFnValue{call: Box::new(log_fn as fn(_, &_) -> _)}) ), vec![Flags::Const_f]).unwrap();
+   env.var_decl("get".to_string(), RuntimeValueServe::Owned(  Box::new(get_native()) ), vec![Flags::Const_f]).unwrap();
 
    env
 }
 
-fn log_fn<'a>(args: Vec<RuntimeValueServe>, scope: &'static RefCell<Scope>) -> RuntimeValueServe {
+fn log_fn(args: Vec<RuntimeValueServe>, scope: &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError> {
     use std::io::Write;
     for arg in args{
-        let value = unwrap_runtime_value_serve(arg.clone(), scope);
+        let value = unwrap_runtime_value_serve(arg.clone(), scope)?;
         let output = format!("{}", value);
         // Process escape sequences (single backslash from source)
         let processed = process_escape_sequences(&output);
         print!("{}", processed);
     }
     // Flush stdout to ensure output appears immediately
-    io::stdout().flush().expect("Failed to flush stdout");
-    return RuntimeValueServe::Owned(Box::new(NilVal{}))
+    io::stdout().flush().map_err(|e| IonError::runtime(format!("Failed to flush stdout: {}", e)))?;
+    Ok(RuntimeValueServe::Owned(Box::new(NilVal{})))
 }
 
 fn process_escape_sequences(s: &str) -> String {
@@ -192,22 +198,16 @@ fn process_escape_sequences(s: &str) -> String {
     result
 }
 
-fn get_fn<'a>(args: Vec<RuntimeValueServe>, _scope: &'static RefCell<Scope>) -> RuntimeValueServe {
-    use std::io::Write;
-    if args.len() > 0{
-        panic!("get_fn() doesn't take any arguments.");
-    }
-    
+#[ion_native]
+fn get() -> Result<String, IonError> {
     // Flush stdout before reading to ensure any prompts are displayed
-    io::stdout().flush().expect("Failed to flush stdout");
+    io::stdout().flush().map_err(|e| IonError::runtime(format!("Failed to flush stdout: {}", e)))?;
     
     let mut input = String::new();
     io::stdin()
         .read_line(&mut input)
-        .expect("Failed to 'get' || read line");
+        .map_err(|e| IonError::runtime(format!("Failed to 'get' || read line: {}", e)))?;
     
     // Trim the newline character(s) from the input
-    let trimmed_input = input.trim_end().to_string();
-    
-    return RuntimeValueServe::Owned(Box::new(StrLiteral{content: trimmed_input}))
+    Ok(input.trim_end().to_string())
 }
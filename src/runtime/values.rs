@@ -4,9 +4,11 @@ use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display};
-use crate::ast::Identifier;
+use std::rc::Rc;
+use crate::ast::{Identifier, Number, Param, Stmt};
+use crate::error::IonError;
 use crate::interpreter::RuntimeValueServe;
-use crate::lexer::Attr;
+use crate::lexer::{Attr, NumKind, Overflow};
 use crate::scopes::Scope;
 
 #[derive(PartialEq)]
@@ -22,16 +24,31 @@ pub enum RuntimeValueType {
     ObjectLiteralVal,
     ArrayVal,
     ArrayLiteralVal,
+    Range,
     
     FnStructVal,
     NativeFn,
 
-    StmtExec
+    StmtExec,
+    Return,
+    Break,
+    Continue,
 }
 
 #[RuntimeValue(RuntimeValueType::StmtExec)]
 pub struct StmtExecS{}
 
+#[RuntimeValue(RuntimeValueType::Break)]
+pub struct BreakSignal{}
+
+#[RuntimeValue(RuntimeValueType::Continue)]
+pub struct ContinueSignal{}
+
+#[RuntimeValue(RuntimeValueType::Return)]
+pub struct ReturnSignal{
+    pub value: RuntimeValueServe,
+}
+
 #[RuntimeValue(RuntimeValueType::Nil)]
 pub struct NilVal{}
 
@@ -55,6 +72,14 @@ impl<'a, T: Num + Debug + Display + Clone + 'static> RuntimeValue for NumericVal
     }
 }
 
+// A number held in the width its variable declares (`<structure: u8>`), rather than the
+// narrowest one that fits; arithmetic on it stays in that width (see `runtime::fixed`)
+#[RuntimeValue(RuntimeValueType::Numeric)]
+pub struct FixedVal{
+    pub value: Number,
+    pub kind: NumKind,
+    pub overflow: Overflow,
+}
 
 #[RuntimeValue(RuntimeValueType::Boolean)]
 pub struct BooleanVal{
@@ -78,6 +103,12 @@ pub struct ArrayLiteralVal{
     pub entries: Vec<RuntimeValueServe>,
 }
 
+#[RuntimeValue(RuntimeValueType::Range)]
+pub struct RangeVal{
+    pub start: i128,
+    pub end: i128,
+}
+
 #[RuntimeValue(RuntimeValueType::ObjectVal)]
 pub struct ObjectVal {
     pub properties: HashMap<String, Attr>,
@@ -121,18 +152,44 @@ impl RuntimeValue for ObjectLiteralVal {
 }
 
 
-#[RuntimeValue(RuntimeValueType::FnStructVal)]
+#[derive(Clone)]
 pub struct FuncStructVal{
-    pub parameters: HashMap<String, Attr>,
+    pub parameters: Vec<Param>,
     pub return_type: Attr,
+    pub body: Vec<Stmt>,
+    pub closure: Rc<RefCell<Scope>>,
+}
+
+impl Debug for FuncStructVal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FuncStructVal")
+            .field("parameters", &self.parameters)
+            .field("return_type", &self.return_type)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+impl RuntimeValue for FuncStructVal {
+    fn Type(&self) -> RuntimeValueType {
+        RuntimeValueType::FnStructVal
+    }
+
+    fn clone_box(&self) -> Box<dyn RuntimeValue> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + 'static) {
+        self
+    }
 }
 
 pub trait Callable: Debug {
     fn call_fn(
         &self,
         args: Vec<RuntimeValueServe>,
-        scope: &'static RefCell<Scope>,
-    ) -> RuntimeValueServe;
+        scope: &Rc<RefCell<Scope>>,
+    ) -> Result<RuntimeValueServe, IonError>;
 
     fn clone_box(&self) -> Box<dyn Callable>;
 }
@@ -145,7 +202,7 @@ impl<'a> Clone for Box<dyn Callable> {
 
 impl<'a, F> Callable for F
 where
-    F: Fn(Vec<RuntimeValueServe>, &'static RefCell<Scope>) -> RuntimeValueServe
+    F: Fn(Vec<RuntimeValueServe>, &Rc<RefCell<Scope>>) -> Result<RuntimeValueServe, IonError>
         + Clone
         + Debug
         + 'static,
@@ -153,8 +210,8 @@ where
     fn call_fn(
         &self,
         args: Vec<RuntimeValueServe>,
-        scope: &'static RefCell<Scope>,
-    ) -> RuntimeValueServe {
+        scope: &Rc<RefCell<Scope>>,
+    ) -> Result<RuntimeValueServe, IonError> {
         self(args, scope)
     }
 
@@ -1,4 +1,5 @@
 use std::fmt;
+use crate::ast::Number;
 use crate::values::*;
 
 pub use crate::runtime::complex_values_impls;
@@ -15,6 +16,16 @@ impl<T: num_traits::Num + fmt::Debug + fmt::Display> fmt::Display for NumericVal
     }
 }
 
+impl fmt::Display for FixedVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Number::Int(n) => write!(f, "{}", n),
+            Number::Float(n) if self.kind == crate::lexer::NumKind::F32 => write!(f, "{}", n as f32),
+            Number::Float(n) => write!(f, "{}", n),
+        }
+    }
+}
+
 impl fmt::Display for StrLiteral {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.content)
@@ -43,6 +54,12 @@ impl fmt::Display for ArrayVal {
     }
 }
 
+impl fmt::Display for RangeVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
 impl fmt::Display for NativeFnValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "<native fn>")
@@ -54,3 +71,21 @@ impl fmt::Display for StmtExecS {
         write!(f, "<stmt execution>")
     }
 }
+
+impl fmt::Display for ReturnSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<return {}>", self.value)
+    }
+}
+
+impl fmt::Display for BreakSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<break>")
+    }
+}
+
+impl fmt::Display for ContinueSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<continue>")
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+const EXPECT_ERROR: &str = "// expect-error:";
+
+// Runs every `.io` script under the given paths (default `tests/`) in its own `ion` process and
+// checks it against its golden files:
+//  - stdout must match the sibling `.out` file (`--bless` rewrites them from the current output)
+//  - a script annotated with `// expect-error: E0004` must fail with that error code; any other
+//    script must run to completion
+// Returns the process exit code: 0 when every script passed.
+pub fn run(args: Vec<String>) -> i32 {
+    let bless = args.iter().any(|arg| arg == "--bless");
+    let mut roots: Vec<PathBuf> = args.iter().filter(|arg| *arg != "--bless").map(PathBuf::from).collect();
+    if roots.is_empty() {
+        roots.push(PathBuf::from("tests"));
+    }
+
+    let mut scripts = vec![];
+    for root in &roots {
+        if let Err(e) = collect_scripts(root, &mut scripts) {
+            eprintln!("error: failed to read [ {} ]: {}", root.display(), e);
+            return 2;
+        }
+    }
+    scripts.sort();
+
+    let ion = env::current_exe().expect("the test runner needs the path of the ion binary");
+    println!("running {} scripts", scripts.len());
+
+    let mut failures = vec![];
+    for script in &scripts {
+        let outcome = check_script(&ion, script, bless);
+        println!("test {} ... {}", script.display(), if outcome.is_ok() { "ok" } else { "FAILED" });
+        if let Err(reason) = outcome {
+            failures.push((script, reason));
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (script, reason) in &failures {
+            println!("\n---- {} ----\n{}", script.display(), reason);
+        }
+    }
+    let passed = scripts.len() - failures.len();
+    let status = if failures.is_empty() { "ok" } else { "FAILED" };
+    println!("\ntest result: {}. {} passed; {} failed", status, passed, failures.len());
+
+    if failures.is_empty() { 0 } else { 1 }
+}
+
+// Every `.io` file under `path` (or `path` itself when it names a file), skipping build output
+// and hidden directories
+pub fn collect_scripts(path: &Path, scripts: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        fs::metadata(path)?;
+        scripts.push(path.to_path_buf());
+        return Ok(());
+    }
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() && (name == "target" || name.starts_with('.')) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_scripts(&path, scripts)?;
+        } else if path.extension().is_some_and(|ext| ext == "io") {
+            scripts.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn check_script(ion: &Path, script: &Path, bless: bool) -> Result<(), String> {
+    let source = fs::read_to_string(script).map_err(|e| format!("failed to read script: {}", e))?;
+    let expected_error = source.lines().find_map(|line| line.trim().strip_prefix(EXPECT_ERROR)).map(str::trim);
+
+    // stdin is closed so scripts calling get() read an empty line instead of blocking
+    let output = Command::new(ion)
+        .arg(script)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run script: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    match (expected_error, output.status.success()) {
+        (Some(code), true) => return Err(format!("expected error {}, but the script finished", code)),
+        (Some(code), false) if !stderr.lines().next().is_some_and(|line| line.contains(&format!("[{}]", code))) => {
+            return Err(format!("expected error {}, found:\n{}", code, stderr.trim_end()));
+        },
+        (None, false) => return Err(format!("unexpected error:\n{}", stderr.trim_end())),
+        _ => {},
+    }
+
+    let golden = script.with_extension("out");
+    if bless {
+        return fs::write(&golden, stdout.as_bytes()).map_err(|e| format!("failed to write [ {} ]: {}", golden.display(), e));
+    }
+    let expected = fs::read_to_string(&golden)
+        .map_err(|_| format!("missing [ {} ]; run `ion test --bless` to record it", golden.display()))?;
+    if expected != stdout {
+        return Err(diff(&expected, &stdout));
+    }
+    Ok(())
+}
+
+// Points at the first line where the output strays from the golden file
+fn diff(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.split('\n').collect();
+    let actual_lines: Vec<&str> = actual.split('\n').collect();
+    let line = expected_lines.iter().zip(&actual_lines).take_while(|(e, a)| e == a).count();
+    format!(
+        "stdout differs from the .out file at line {}\n  expected: {:?}\n     found: {:?}",
+        line + 1,
+        expected_lines.get(line).unwrap_or(&"<end of output>"),
+        actual_lines.get(line).unwrap_or(&"<end of output>"),
+    )
+}
@@ -0,0 +1,88 @@
+// Checks the static pass `Interpreter::eval` runs first: every mismatch is found up front,
+// including in code that never runs, and the .io scripts meant to succeed check cleanly.
+use std::fs;
+
+use ion::{parser, Interpreter};
+
+fn check(source: &str) -> Vec<String> {
+    let program = parser::prod_ast(source.to_string()).unwrap();
+    Interpreter::new().check(&program).iter().map(|err| err.msg.clone()).collect()
+}
+
+#[test]
+fn every_mismatch_is_reported() {
+    let errors = check("\
+| s <asg> <structure: string> 1 + 2;
+| p <asg> <structure: object> obj { x: numeric; };
+| q <asg> <structure: complex> <complex: p> { x: \"one\"; };
+| f <asg> <structure: function> fn (n: numeric) @ bool { return n < 1; };
+if false {
+    f(1, 2);
+}
+");
+    assert_eq!(errors, [
+        "Incorrect Type Assignement | Expected: String | Found: numeric",
+        "Incorrect Type Assignement | Expected: Numeric | Found: string",
+        "Function [ f ] expected 1 argument(s), found 2",
+    ]);
+}
+
+#[test]
+fn mismatched_operands_are_reported_but_nil_ones_give_nil() {
+    let errors = check("\
+| n <asg> <structure: numeric> \"a\" + 1;
+| m <asg> <structure: numeric> nil * 2;
+| s <asg> <structure: string> \"a\" + \"b\";
+");
+    assert_eq!(errors, [
+        "Operator [ + ] expects two numeric or two string operands | Found: string + numeric",
+    ]);
+}
+
+#[test]
+fn omitted_structure_is_inferred() {
+    let errors = check("\
+| n <asg> 1;
+n <asg> \"one\";
+| o <asg> { a: 1; };
+log(o.b);
+| x <asg> nil;
+");
+    assert_eq!(errors, [
+        "Incorrect Type Assignement | Expected: Numeric | Found: string",
+        "Property 'b' not found",
+        "Missing flag <structure> on [ x ]",
+    ]);
+}
+
+#[test]
+fn fixed_width_literals_are_range_checked() {
+    let errors = check("\
+| b <asg> <structure: u8> 255;
+b <asg> 256;
+| f <asg> fn (n: i8) @ i8 { return n; };
+f(-129);
+");
+    assert_eq!(errors, ["Value 256 is out of range for <structure: u8>", "Value -129 is out of range for <structure: i8>"]);
+}
+
+#[test]
+fn nothing_runs_when_checking_fails() {
+    let mut interpreter = Interpreter::new();
+    let result = interpreter.eval_str("| x <asg> <structure: numeric> 1; x <asg> \"one\";");
+    assert!(result.is_err());
+    assert!(interpreter.get_global("x").is_err());
+}
+
+#[test]
+fn scripts_check_cleanly() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests");
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let source = fs::read_to_string(&path).unwrap_or_default();
+        if path.extension().is_none_or(|ext| ext != "io") || source.contains("expect-error") {
+            continue;
+        }
+        assert_eq!(check(&source), Vec::<String>::new(), "{} doesn't type check", path.display());
+    }
+}
@@ -0,0 +1,20 @@
+// Runs the `ion` binary the way a shell would.
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn double_dash_runs_a_script_named_like_a_subcommand() {
+    let dir = std::env::temp_dir().join(format!("ion-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("test"), "log(\"ran the script\");").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ion"))
+        .current_dir(&dir)
+        .args(["--", "test"])
+        .output()
+        .expect("failed to run `ion`");
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ran the script");
+}
@@ -0,0 +1,66 @@
+// Drives the embedding API the way a host would: globals in and out, natives registered by the host.
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ion::error::ErrorKind;
+use ion::interpreter::{unwrap_runtime_value_serve, RuntimeValueServe};
+use ion::scopes::Scope;
+use ion::values::{NumericVal, StrLiteral};
+use ion::Interpreter;
+
+#[test]
+fn globals_set_by_the_host_are_typed_like_declarations() {
+    let mut ion = Interpreter::new();
+    ion.set_global("base", Box::new(NumericVal{value: 21u8})).unwrap();
+
+    ion.eval_str("base <asg> base * 2;").unwrap();
+    assert_eq!(ion.get_global("base").unwrap().to_string(), "42");
+
+    // The inferred <structure: numeric> holds for scripts and for the host alike
+    let err = ion.eval_str("base <asg> \"text\";").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Type);
+    let err = ion.set_global("base", Box::new(StrLiteral{content: "text".to_string()})).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Type);
+    assert_eq!(ion.get_global("base").unwrap().to_string(), "42");
+
+    ion.set_global("base", Box::new(NumericVal{value: 7u8})).unwrap();
+    assert_eq!(ion.eval_str("base + 1;").unwrap().to_string(), "8");
+}
+
+#[test]
+fn registered_closures_are_callable_from_scripts() {
+    let mut ion = Interpreter::new();
+    let calls = Rc::new(RefCell::new(0));
+    let counter = Rc::clone(&calls);
+    ion.register_native("shout", move |args: Vec<RuntimeValueServe>, scope: &Rc<RefCell<Scope>>| {
+        *counter.borrow_mut() += 1;
+        let mut out = String::new();
+        for arg in args {
+            out += &unwrap_runtime_value_serve(arg, scope)?.to_string().to_uppercase();
+        }
+        Ok(RuntimeValueServe::Owned(Box::new(StrLiteral{content: out})))
+    }).unwrap();
+
+    let result = ion.eval_str("| name <asg> <structure: string> \"ion\"; shout(\"hi \", name);").unwrap();
+    assert_eq!(result.to_string(), "HI ION");
+    assert_eq!(*calls.borrow(), 1);
+}
+
+#[test]
+fn missing_globals_are_name_errors() {
+    let ion = Interpreter::new();
+    let err = ion.get_global("nowhere").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Name);
+}
+
+#[test]
+fn names_can_only_be_registered_once() {
+    let mut ion = Interpreter::new();
+    let zero = |_: Vec<RuntimeValueServe>, _: &Rc<RefCell<Scope>>| Ok(RuntimeValueServe::Owned(Box::new(NumericVal{value: 0u8})));
+    ion.register_native("twice", zero).unwrap();
+    assert_eq!(ion.register_native("twice", zero).unwrap_err().kind, ErrorKind::Name);
+    // Natives are const, so scripts can't replace them either
+    assert!(ion.eval_str("twice <asg> 1;").is_err());
+    // Built-ins are registered the same way
+    assert_eq!(ion.register_native("log", zero).unwrap_err().kind, ErrorKind::Name);
+}
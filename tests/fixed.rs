@@ -0,0 +1,18 @@
+// Checks fixed-width arithmetic on operands the checker would normally have rejected.
+use ion::ast::Number;
+use ion::fixed::eval_fixed_bin_expr;
+use ion::lexer::{NumKind, Overflow};
+use ion::values::{BooleanVal, FixedVal, StrLiteral};
+
+#[test]
+fn non_numeric_operands_are_type_errors() {
+    for kind in [NumKind::U8, NumKind::F32, NumKind::F64] {
+        let value = if kind.is_float() { Number::Float(1.0) } else { Number::Int(1) };
+        let fixed = FixedVal{value, kind, overflow: Overflow::Checked};
+        let text = StrLiteral{content: "a".to_string()};
+        let err = eval_fixed_bin_expr(&fixed, &text, "+").unwrap().unwrap_err();
+        assert_eq!(err.kind.code(), "E0003", "{:?} + string", kind);
+        let err = eval_fixed_bin_expr(&BooleanVal{val: true}, &fixed, "*").unwrap().unwrap_err();
+        assert_eq!(err.kind.code(), "E0003", "bool * {:?}", kind);
+    }
+}
@@ -0,0 +1,81 @@
+// Checks `ion fmt`: canonical flags and spacing, comments surviving, and formatting being stable
+// over the .io scripts in this directory.
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use ion::formatter::format_source;
+
+#[test]
+fn flags_are_sorted_and_spaced() {
+    let formatted = format_source("| x <structure:numeric><const> <asg> 2;\n| o <complex: anonymous > <asg><structure: complex> {a:1;};\n").unwrap();
+    assert_eq!(
+        formatted,
+        "| x <asg> <const> <structure: numeric> 2;\n| o <asg> <structure: complex> <complex: anonymous> { a: 1; };\n"
+    );
+}
+
+#[test]
+fn comments_and_blank_lines_are_kept() {
+    let source = "\
+// header
+
+| x <asg> <structure: numeric> 1; // trailing
+
+
+| f <asg> <structure: function> fn () @ numeric { // opening
+    /* inside */
+    x;
+    // before the brace
+};
+| o <asg> <structure: object> // moved behind the statement
+obj {
+    a: numeric;
+};
+";
+    let expected = "\
+// header
+
+| x <asg> <structure: numeric> 1; // trailing
+
+| f <asg> <structure: function> fn () @ numeric { // opening
+    /* inside */
+    x;
+    // before the brace
+};
+| o <asg> <structure: object> obj { a: numeric; }; // moved behind the statement
+";
+    assert_eq!(format_source(source).unwrap(), expected);
+}
+
+#[test]
+fn scripts_format_stably() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests");
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "io") {
+            continue;
+        }
+        // Scripts that are expected to fail may not parse at all
+        let Ok(formatted) = format_source(&fs::read_to_string(&path).unwrap()) else {
+            continue;
+        };
+        assert_eq!(format_source(&formatted).unwrap(), formatted, "{} formats differently twice", path.display());
+    }
+}
+
+#[test]
+fn check_fails_on_unformatted_input() {
+    let check = |source: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ion"))
+            .args(["fmt", "--check", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .expect("failed to run `ion fmt`");
+        child.stdin.take().unwrap().write_all(source.as_bytes()).unwrap();
+        child.wait().unwrap().success()
+    };
+    assert!(check("| x <asg> <structure: numeric> 1;\n"));
+    assert!(!check("| x <asg><structure:numeric> 1;\n"));
+}
@@ -0,0 +1,15 @@
+// Runs `ion test` over the .io scripts in this directory so `cargo test` checks them against
+// their .out files and `// expect-error:` annotations.
+use std::process::Command;
+
+#[test]
+fn scripts_match_golden_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ion"))
+        .arg("test")
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests"))
+        .output()
+        .expect("failed to run `ion test`");
+
+    let report = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", report);
+}
@@ -0,0 +1,126 @@
+// Pins the token stream, spans included, so the lexer keeps producing exactly what the parser
+// and diagnostics expect. The expected streams were recorded with the lexer the cursor rewrite replaced.
+use ion::lexer::{tokenize, Attr, Flags, TokenType};
+
+fn assert_tokens(source: &str, expected: Vec<(&str, TokenType, (isize, isize))>) {
+    let tokens: Vec<(String, TokenType, (isize, isize))> = tokenize(source.to_string()).unwrap()
+        .into_iter()
+        .map(|tok| (tok.value, tok.value_type, tok.loc))
+        .collect();
+    let expected: Vec<(String, TokenType, (isize, isize))> = expected.into_iter()
+        .map(|(value, value_type, loc)| (value.to_string(), value_type, loc))
+        .collect();
+    assert_eq!(tokens, expected);
+}
+
+// Columns count chars, not bytes, and comments (line or block, spanning lines) leave no tokens
+#[test]
+fn multi_byte_text_and_comments() {
+    assert_tokens(r#"// héllo wörld
+| café <asg> <structure: numeric> <const> 12.5;
+log("naïve ✓", café * 3); /* multi
+line */ x <asg> 7;
+"#, vec![
+        ("|", TokenType::Let_k, (2, 0)),
+        ("café", TokenType::Identifier, (2, 2)),
+        ("<asg>", TokenType::Flag(Flags::Assign_f), (2, 7)),
+        ("<structure: numeric>", TokenType::Flag(Flags::Struct_f(Attr::Numeric)), (2, 13)),
+        ("<const>", TokenType::Flag(Flags::Const_f), (2, 34)),
+        ("12.5", TokenType::Number, (2, 42)),
+        (";", TokenType::Semicolon, (2, 46)),
+        ("log", TokenType::Identifier, (3, 0)),
+        ("(", TokenType::LeftParen, (3, 3)),
+        ("naïve ✓", TokenType::String, (3, 4)),
+        (",", TokenType::Comma, (3, 13)),
+        ("café", TokenType::Identifier, (3, 15)),
+        ("*", TokenType::BinOp, (3, 20)),
+        ("3", TokenType::Number, (3, 22)),
+        (")", TokenType::RightParen, (3, 23)),
+        (";", TokenType::Semicolon, (3, 24)),
+        ("x", TokenType::Identifier, (4, 8)),
+        ("<asg>", TokenType::Flag(Flags::Assign_f), (4, 10)),
+        ("7", TokenType::Number, (4, 16)),
+        (";", TokenType::Semicolon, (4, 17)),
+        ("", TokenType::EOF, (5, 0)),
+    ]);
+}
+
+#[test]
+fn flags_keywords_and_operators() {
+    assert_tokens(r#"| p <asg> <structure: complex> <complex: point> { x: 1; };
+	for i in 0..10 { if i != 2 && !done { break; } else { continue; } }
+| f <asg> <structure: function> fn (n: numeric) @ bool { return n >= 1.25; };
+a[0].b <asg> nil; true || false;
+"#, vec![
+        ("|", TokenType::Let_k, (1, 0)),
+        ("p", TokenType::Identifier, (1, 2)),
+        ("<asg>", TokenType::Flag(Flags::Assign_f), (1, 4)),
+        ("<structure: complex>", TokenType::Flag(Flags::Struct_f(Attr::ComplexKind)), (1, 10)),
+        ("<complex: point>", TokenType::Flag(Flags::Complex_f(Attr::Complex("point".to_string()))), (1, 31)),
+        ("{", TokenType::LeftCurly, (1, 48)),
+        ("x", TokenType::Identifier, (1, 50)),
+        (":", TokenType::Colon, (1, 51)),
+        ("1", TokenType::Number, (1, 53)),
+        (";", TokenType::Semicolon, (1, 54)),
+        ("}", TokenType::RightCurly, (1, 56)),
+        (";", TokenType::Semicolon, (1, 57)),
+        ("for", TokenType::For_k, (2, 1)),
+        ("i", TokenType::Identifier, (2, 5)),
+        ("in", TokenType::In_k, (2, 7)),
+        ("0", TokenType::Number, (2, 10)),
+        ("..", TokenType::DotDot, (2, 11)),
+        ("10", TokenType::Number, (2, 13)),
+        ("{", TokenType::LeftCurly, (2, 16)),
+        ("if", TokenType::If_k, (2, 18)),
+        ("i", TokenType::Identifier, (2, 21)),
+        ("!=", TokenType::BinOp, (2, 23)),
+        ("2", TokenType::Number, (2, 26)),
+        ("&&", TokenType::BinOp, (2, 28)),
+        ("!", TokenType::BinOp, (2, 31)),
+        ("done", TokenType::Identifier, (2, 32)),
+        ("{", TokenType::LeftCurly, (2, 37)),
+        ("break", TokenType::Break_k, (2, 39)),
+        (";", TokenType::Semicolon, (2, 44)),
+        ("}", TokenType::RightCurly, (2, 46)),
+        ("else", TokenType::Else_k, (2, 48)),
+        ("{", TokenType::LeftCurly, (2, 53)),
+        ("continue", TokenType::Continue_k, (2, 55)),
+        (";", TokenType::Semicolon, (2, 63)),
+        ("}", TokenType::RightCurly, (2, 65)),
+        ("}", TokenType::RightCurly, (2, 67)),
+        ("|", TokenType::Let_k, (3, 0)),
+        ("f", TokenType::Identifier, (3, 2)),
+        ("<asg>", TokenType::Flag(Flags::Assign_f), (3, 4)),
+        ("<structure: function>", TokenType::Flag(Flags::Struct_f(Attr::Function)), (3, 10)),
+        ("fn", TokenType::fn_struct_k, (3, 32)),
+        ("(", TokenType::LeftParen, (3, 35)),
+        ("n", TokenType::Identifier, (3, 36)),
+        (":", TokenType::Colon, (3, 37)),
+        ("numeric", TokenType::Identifier, (3, 39)),
+        (")", TokenType::RightParen, (3, 46)),
+        ("@", TokenType::RetType, (3, 48)),
+        ("bool", TokenType::Identifier, (3, 50)),
+        ("{", TokenType::LeftCurly, (3, 55)),
+        ("return", TokenType::Return_k, (3, 57)),
+        ("n", TokenType::Identifier, (3, 64)),
+        (">=", TokenType::BinOp, (3, 66)),
+        ("1.25", TokenType::Number, (3, 69)),
+        (";", TokenType::Semicolon, (3, 73)),
+        ("}", TokenType::RightCurly, (3, 75)),
+        (";", TokenType::Semicolon, (3, 76)),
+        ("a", TokenType::Identifier, (4, 0)),
+        ("[", TokenType::LeftBrace, (4, 1)),
+        ("0", TokenType::Number, (4, 2)),
+        ("]", TokenType::RightBrace, (4, 3)),
+        (".", TokenType::Dot, (4, 4)),
+        ("b", TokenType::Identifier, (4, 5)),
+        ("<asg>", TokenType::Flag(Flags::Assign_f), (4, 7)),
+        ("nil", TokenType::Nil_k, (4, 13)),
+        (";", TokenType::Semicolon, (4, 16)),
+        ("true", TokenType::Bool_true_t, (4, 18)),
+        ("||", TokenType::BinOp, (4, 23)),
+        ("false", TokenType::Bool_false_t, (4, 26)),
+        (";", TokenType::Semicolon, (4, 31)),
+        ("", TokenType::EOF, (5, 0)),
+    ]);
+}
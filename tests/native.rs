@@ -0,0 +1,24 @@
+// Checks how values cross between Rust natives and scripts.
+use ion::error::ErrorKind;
+use ion::{ion_native, Interpreter};
+
+#[ion_native]
+fn pow(base: f64, exp: i32) -> f64 {
+    base.powi(exp)
+}
+
+#[test]
+fn float_results_keep_float_arithmetic() {
+    let mut ion = Interpreter::new();
+    ion.register("pow", pow_native()).unwrap();
+    // 4.0 from pow stays a float, so this is 0.5 rather than a truncated 0
+    assert_eq!(ion.eval_str("pow(2, 2) / 8;").unwrap().to_string(), "0.5");
+}
+
+#[test]
+fn integer_arguments_are_range_checked() {
+    let mut ion = Interpreter::new();
+    ion.register("pow", pow_native()).unwrap();
+    let err = ion.eval_str("pow(2, 4294967296);").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Type, "{}", err.msg);
+}
@@ -0,0 +1,59 @@
+// Checks that `ast::printer` emits source the parser reads back into the same AST, both for
+// the .io scripts in this directory and for the spots where the grammar needs parentheses.
+use std::fs;
+
+use ion::ast::printer::print;
+use ion::parser::prod_ast;
+
+fn assert_round_trip(source: &str) {
+    let program = prod_ast(source.to_string()).unwrap_or_else(|e| panic!("{}\n{}", e.msg, source));
+    let printed = print(&program);
+    let reparsed = prod_ast(printed.clone()).unwrap_or_else(|e| panic!("printed source doesn't parse: {}\n{}", e.msg, printed));
+    assert!(reparsed == program, "round trip changed the AST:\n{}\n---\n{}", source, printed);
+    assert_eq!(print(&reparsed), printed, "printing isn't stable");
+}
+
+#[test]
+fn scripts_round_trip() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests");
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "io") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap();
+        // Scripts that are expected to fail may do so before parsing finishes
+        if prod_ast(source.clone()).is_ok() {
+            assert_round_trip(&source);
+        }
+    }
+}
+
+#[test]
+fn parenthesised_expressions_round_trip() {
+    for source in [
+        "a * (b + c);",
+        "a - (b - c);",
+        "(a - b) - c;",
+        "a * (b.c);",
+        "a * (f(x));",
+        "-a * b;",
+        "a + -b;",
+        "!a.b;",
+        "(!a).b;",
+        "f().g;",
+        "(f().g)();",
+        "f()(x)[0].y;",
+        "(\"a\" + b) - c;",
+        "(0..n).x;",
+        "(a || b) && c;",
+        "([1, 2])[0];",
+        "(fn (x: numeric) @ numeric { return x * 2; })(3);",
+        "| p <asg> <const> <structure: complex> <complex: point> { x: 1; y: -2.5; };",
+        "| o <asg> <structure: object> obj { x: numeric; y: point; };",
+        "| a <asg> <structure: array> arr [complex; point; 3;];",
+        "if a { return; } else if b { break; } else { continue; }",
+    ] {
+        assert_round_trip(source);
+    }
+}
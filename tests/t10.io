@@ -0,0 +1,10 @@
+| add <asg> <structure: function> <const> fn (a: numeric, b: numeric) @ numeric { //Functions are values too; params and the return type take in attribute arguments
+    | sum <asg> <structure: numeric> a + b;
+    sum * 2;
+};
+
+log(add(1, 2), "\n"); //The last statement of the body is handed back to the caller
+
+| obj_e <asg> <structure: object> obj { x: numeric; };
+| get_x <asg> <structure: function> fn (o: obj_e) @ numeric { o.x; }; //Complex params are type checked against their struct
+log(get_x({x: 7;}), "\n");
@@ -0,0 +1,10 @@
+| half <asg> <structure: function> fn (n: numeric) @ numeric {
+    return n / 2; //return hands the value back right away...
+    log("never reached\n");
+};
+log(half(10), "\n");
+
+| shout <asg> <structure: function> fn (s: string) @ string {
+    return s + "!"; //...and is type checked against the function's return attr
+};
+log(shout("hey"), "\n");
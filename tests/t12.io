@@ -0,0 +1,21 @@
+| ready <asg> <structure: bool> true;
+| count <asg> <structure: numeric> 0;
+
+if ready { //Conditions must be of type bool; anything else is an error
+    | inner <asg> <structure: numeric> 5; //Declarations stay inside of the block
+    count <asg> count + inner;
+} else {
+    log("not ready\n");
+}
+log(count, "\n");
+
+| sign <asg> <structure: function> fn (neg: bool, zero: bool) @ string {
+    if zero {
+        return "zero";
+    } else if neg {
+        return "negative";
+    } else {
+        return "positive";
+    }
+};
+log(sign(false, true), " ", sign(true, false), " ", sign(false, false), "\n");
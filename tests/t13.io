@@ -0,0 +1,14 @@
+| small <asg> <structure: numeric> 200; //Minimized as u8...
+| big <asg> <structure: numeric> 200.5; //...and f32, yet they still compare by value
+log(small < big, " ", small >= 200, " ", small == 200.0, " ", big != 200.5, "\n");
+
+log("ion" == "ion", " ", nil == nil, " ", true != false, "\n"); //Equality is structural
+
+| a <asg> <structure: complex> <complex: anonymous> { x: 1; y: [1, 2]; };
+| b <asg> <structure: complex> <complex: anonymous> { x: 1; y: [1, 2]; };
+| c <asg> <structure: complex> <complex: anonymous> [1, 2, 3];
+log(a == b, " ", a.y == c, "\n");
+
+if 1 + 1 == 2 {
+    log("math works\n");
+}
@@ -0,0 +1,10 @@
+| age <asg> <structure: numeric> 20;
+| member <asg> <structure: bool> false;
+log(age >= 18 && !member, " ", age < 18 || member, "\n"); //&& binds tighter than ||, and both bind looser than comparisons
+
+| loud <asg> <structure: function> fn () @ bool {
+    log("evaluated! ");
+    return true;
+};
+log(false && loud(), " ", true || loud(), "\n"); //The right hand side only runs when it has to
+log(true && loud(), "\n");
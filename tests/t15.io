@@ -0,0 +1,25 @@
+| i <asg> <structure: numeric> 0;
+| total <asg> <structure: numeric> 0;
+
+while i < 10 { //Assignments inside the loop body update the outer variables
+    i <asg> i + 1;
+    if i % 2 == 0 {
+        continue; //Skip straight to the next iteration...
+    }
+    if i > 7 {
+        break; //...or leave the loop altogether
+    }
+    total <asg> total + i;
+}
+log(i, " ", total, "\n");
+
+| first_over <asg> <structure: function> fn (limit: numeric) @ numeric {
+    | n <asg> <structure: numeric> 1;
+    while true {
+        if n > limit {
+            return n; //return unwinds out of the loop as well
+        }
+        n <asg> n * 2;
+    }
+};
+log(first_over(100), "\n");
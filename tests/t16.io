@@ -0,0 +1,19 @@
+| names <asg> <structure: complex> <complex: anonymous> ["ion", "rust", "zig"];
+for name in names { //The loop variable takes on the structure of each element...
+    log(name, " ");
+}
+log("\n");
+
+| sum <asg> <structure: numeric> 0;
+for i in 0..5 { //...and ranges are walked lazily, without building an array
+    sum <asg> sum + i;
+}
+log(sum, "\n");
+
+| people <asg> <structure: complex> <complex: anonymous> [{ age: 30; }, { age: 12; }];
+for p in people {
+    if p.age < 18 {
+        continue;
+    }
+    log(p.age, "\n");
+}
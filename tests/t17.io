@@ -0,0 +1,11 @@
+// expect-error: E0004
+| x <asg> <structure: numeric> 10;
+log(x / 2, "\n");
+
+//Errors no longer crash the interpreter; they are rendered with their code, location and source, e.g.
+//  runtime error[E0004]: Division by zero
+//     --> tests/t17.io:11:5
+//     |
+//  11 | log(x / 0, "\n");
+//     |     ^^^^^
+log(x / 0, "\n");
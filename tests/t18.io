@@ -0,0 +1,8 @@
+//Run with: ion tests/t18.io hello world
+//Everything after the script path is handed to it through the global (const) `args` array
+for arg in args {
+    log(arg, "\n");
+}
+
+| greeting <asg> <structure: string> "args given: ";
+log(greeting, args, "\n");
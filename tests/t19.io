@@ -0,0 +1,9 @@
+// expect-error: E0003
+//Types are checked before anything runs, so nothing below gets logged; every mismatch is reported at once
+log("unreachable\n");
+| s <asg> <structure: string> 1 + 2;
+
+| half <asg> <structure: function> fn (n: numeric) @ numeric {
+    return n / 2;
+};
+log(half("four"));
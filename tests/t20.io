@@ -0,0 +1,20 @@
+//<structure> can be left out when the initializer says what it is; the inferred flag is recorded as if it were written
+| count <asg> 3;
+| name <asg> "ion";
+| point <asg> { x: 1; y: 2; };
+| primes <asg> [2, 3, 5];
+| double <asg> fn (n: numeric) @ numeric {
+    return n * 2;
+};
+
+count <asg> double(count);
+log(name, " ", count, " ", point.x + point.y, "\n");
+for p in primes {
+    log(p, " ");
+}
+log("\n");
+
+//A <complex> flag alone implies <structure: complex>
+| pair <asg> <structure: array> arr [numeric; nil; 2;];
+| origin <asg> <complex: pair> [0, 0];
+log(origin[1], "\n");
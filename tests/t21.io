@@ -0,0 +1,17 @@
+// expect-error: E0004
+//Fixed-width numerics keep their declared type through arithmetic; <overflow: ..> picks what happens when a result doesn't fit
+| level <asg> <structure: u8> 200;
+| tick <asg> <structure: u8> <overflow: wrapping> 250;
+| gain <asg> <structure: i8> <overflow: saturating> 100;
+| total <asg> <structure: i64> 7;
+
+tick <asg> tick + 10;
+log(tick, " ", gain * 2, " ", (-gain) * 2, " ", total / 2, " ", level % 7, "\n");
+
+| scale <asg> <structure: function> fn (x: u16) @ u16 {
+    return x * 300;
+};
+log(scale(200), "\n");
+
+//Checked is the default: this one fails at runtime
+log(level + 100, "\n");
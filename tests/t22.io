@@ -0,0 +1,17 @@
+// expect-error: E0004
+//Integer arithmetic is exact across the whole u64 / i64 range; only a float operand makes it float arithmetic
+| max <asg> 18446744073709551615;
+| odd <asg> 9007199254740993;
+log(max - 1, " ", odd + 2, "\n");
+
+//Division truncates toward zero and the remainder takes the dividend's sign
+log(7 / 2, " ", (-7) / 2, " ", (-7) % 3, " ", 7.0 / 2, "\n");
+
+//Ranges and indexes take the same exact integers
+for i in 18446744073709551613..max {
+    log(i, " ");
+}
+log("\n");
+
+//Leaving the 64 bit range is an error, not a rounded float
+log(max + 1, "\n");
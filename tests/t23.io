@@ -0,0 +1,18 @@
+| total <asg> <structure: numeric> 0;
+for i in 0..3 { //Assigning the loop variable itself stores its value, not its name
+    total <asg> i;
+}
+log(total, "\n");
+
+| names <asg> <structure: complex> <complex: anonymous> ["ada", "grace", "linus"];
+| last <asg> <structure: string> nil;
+for n in names {
+    last <asg> n;
+}
+log(last, "\n");
+
+| pairs <asg> <structure: complex> <complex: anonymous> [];
+for i in 0..2 {
+    pairs <asg> { at: i; };
+}
+log(pairs.at, "\n");
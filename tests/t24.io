@@ -0,0 +1,14 @@
+| mk <asg> <structure: function> fn (n: numeric) @ anonymous {
+    return { v: n; pair: [n, n + 1]; }; //Names inside the result are resolved before n goes away
+};
+| made <asg> <structure: complex> <complex: anonymous> mk(3);
+log(made.v, " ", made.pair, "\n");
+log(mk(4).v, "\n");
+
+| g <asg> <structure: numeric> 0;
+| set <asg> <structure: function> fn (n: numeric) @ numeric {
+    g <asg> n; //A global set from a parameter keeps the value after the call
+    return n;
+};
+set(5);
+log(g, "\n");
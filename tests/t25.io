@@ -0,0 +1,3 @@
+// expect-error: E0004
+//A literal past 64 bits is an overflow, not a saturated u64
+| big <asg> <structure: numeric> 18446744073709551616;
@@ -0,0 +1,4 @@
+// expect-error: E0004
+| a <asg> <structure: complex> <complex: anonymous> [1, 2];
+log(a[1], "\n");
+log(a[-1], "\n"); //A negative index is out of bounds, not wrapped around
@@ -0,0 +1,6 @@
+// expect-error: E0003
+//An anonymous value's type is only known once it runs, and mixing it with a number is still a type error
+| label <asg> <structure: complex> <complex: anonymous> "a";
+| none <asg> <structure: numeric> nil;
+log(none + 1, " ", nil * 2, "\n"); //A nil operand gives nil, as the checker also expects
+log(label + 1, "\n");
@@ -0,0 +1,3 @@
+// expect-error: E0001
+//Only ASCII digits make a number, so a superscript is an unknown token rather than a crash
+| x <asg> ²;
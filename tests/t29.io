@@ -0,0 +1,4 @@
+//Digits may follow an identifier's first character, as in the u8 .. f64 type names
+| item2 <asg> <structure: numeric> 3;
+| x_10 <asg> <structure: u8> 10;
+log(item2 + x_10, "\n");
@@ -1,3 +1,4 @@
+// expect-error: E0003
 | obj_e <asg> <structure: object> //Error msgs help!
 obj { 
     x: numeric; 
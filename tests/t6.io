@@ -1,3 +1,4 @@
+// expect-error: E0002
 | my_obj <structure:complex> <complex:anonymous> {a:12;} ; //The complex attribute argumentl: anonymous - bypasses Ion's inbuilt type safety;
 log(my_obj)
 	
@@ -1,3 +1,4 @@
+// expect-error: E0005
 | x <asg> <structure: numeric> 2; 
 | y <asg> <structure: numeric> y + 2; 
 log(y);
@@ -0,0 +1,130 @@
+// Checks that the generated `walk_*` functions reach every node, and that `VisitorMut` edits stick.
+use std::collections::BTreeSet;
+
+use ion::ast::printer::print;
+use ion::ast::visit::{walk_expr, walk_property_literal, walk_stmt};
+use ion::ast::{Expr, Identifier, Number, NumericLiteral, Param, Property, PropertyLiteral, Stmt, Visitor, VisitorMut};
+use ion::parser::prod_ast;
+
+// Every `Stmt` and `Expr` kind, with the children that are easiest to miss named after where they sit
+const SCRIPT: &str = r#"
+| shape <asg> <structure: object> obj { struct_field: numeric; };
+| pair <asg> <structure: array> arr [numeric; nil; 2;];
+| f <asg> <structure: function> fn (param: numeric) @ numeric {
+    while !while_cond && param < 1 {
+        break;
+    }
+    for item in for_iterable {
+        continue;
+    }
+    if if_cond {
+        return member_obj.member_prop;
+    } else {
+        else_arr[0];
+    }
+    return -1;
+};
+asg_target <asg> { literal_key: "text"; list: [nil, true]; span: range_start..range_end; };
+f(call_arg);
+"#;
+
+#[derive(Default)]
+struct Census {
+    kinds: BTreeSet<String>,
+    names: BTreeSet<String>,
+}
+
+// `VarDecl(VarDeclaration { .. })` -> `VarDecl`
+fn variant(node: &impl std::fmt::Debug) -> String {
+    format!("{:?}", node).split('(').next().unwrap().to_string()
+}
+
+impl Visitor for Census {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        self.kinds.insert(format!("Stmt::{}", variant(stmt)));
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        self.kinds.insert(format!("Expr::{}", variant(expr)));
+        walk_expr(self, expr);
+    }
+
+    fn visit_identifier(&mut self, node: &Identifier) {
+        self.names.insert(node.symbol.clone());
+    }
+
+    fn visit_param(&mut self, node: &Param) {
+        self.names.insert(node.param.clone());
+    }
+
+    fn visit_property(&mut self, node: &Property) {
+        self.names.insert(node.key.clone());
+    }
+
+    fn visit_property_literal(&mut self, node: &PropertyLiteral) {
+        self.names.insert(node.key.clone());
+        walk_property_literal(self, node);
+    }
+}
+
+#[test]
+fn every_node_is_visited() {
+    let program = prod_ast(SCRIPT.to_string()).unwrap();
+    let mut census = Census::default();
+    program.accept(&mut census);
+
+    let stmts = ["VarDecl", "VarAsg", "Return", "If", "While", "For", "Break", "Continue", "Expr"];
+    let exprs = [
+        "Numeric", "Str", "Identifier", "Nil", "Bool", "Binary", "Unary", "Range", "Object",
+        "ObjectLiteral", "Array", "ArrayLiteral", "Member", "ArrMember", "FnStruct", "Call",
+    ];
+    let expected: BTreeSet<String> = stmts.iter().map(|kind| format!("Stmt::{}", kind))
+        .chain(exprs.iter().map(|kind| format!("Expr::{}", kind)))
+        .collect();
+    assert_eq!(census.kinds, expected);
+
+    for name in [
+        "struct_field", "param", "while_cond", "for_iterable", "if_cond", "member_obj", "member_prop",
+        "else_arr", "asg_target", "literal_key", "range_start", "range_end", "call_arg",
+    ] {
+        assert!(census.names.contains(name), "[ {} ] wasn't visited", name);
+    }
+}
+
+// Doubles every number and renames one variable, in place
+struct Rewrite;
+
+impl VisitorMut for Rewrite {
+    fn visit_numeric_literal(&mut self, node: &mut NumericLiteral) {
+        if let Number::Int(n) = node.value {
+            node.value = Number::Int(n * 2);
+        }
+    }
+
+    fn visit_identifier(&mut self, node: &mut Identifier) {
+        if node.symbol == "total" {
+            node.symbol = "sum".to_string();
+        }
+    }
+}
+
+#[test]
+fn mutating_visitors_rewrite_the_tree() {
+    let mut program = prod_ast("\
+| total <asg> <structure: numeric> 1;
+while total < 10 {
+    total <asg> total + 3;
+}
+log(total.x, [total, 4]);
+".to_string()).unwrap();
+    program.accept_mut(&mut Rewrite);
+
+    assert_eq!(print(&program), "\
+| total <asg> <structure: numeric> 2;
+while sum < 20 {
+    sum <asg> sum + 6;
+}
+log(sum.x, [sum, 8]);
+");
+}